@@ -1,9 +1,10 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex};
 use tauri::{
-    webview::{PageLoadEvent, WebviewBuilder},
+    webview::{FindOptions, PageLoadEvent, WebviewBuilder},
     AppHandle, Emitter, LogicalPosition, LogicalSize, Manager, Url, Webview, WebviewUrl,
+    WindowBuilder,
 };
 
 /// Represents a browser session with its metadata
@@ -14,6 +15,13 @@ pub struct BrowserSession {
     pub current_url: String,
     pub title: String,
     pub is_loading: bool,
+    /// Label of the window this session's webview is currently embedded in -
+    /// `"main"` unless it's been torn off via `browser_detach_to_window` or
+    /// moved to another window via `browser_reparent`.
+    pub parent_window: String,
+    /// Current zoom factor (1.0 = 100%), kept here so it survives navigation
+    /// and reparenting without having to re-query the webview.
+    pub zoom_level: f64,
 }
 
 /// Event payload for browser URL changes - sent to frontend
@@ -30,15 +38,76 @@ pub struct BrowserLoadingEvent {
     pub is_loading: bool,
 }
 
+/// Event payload emitted when `on_navigation` cancels a load.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrowserNavigationBlockedEvent {
+    pub session_id: String,
+    pub url: String,
+}
+
+/// Event payload for the result of a `browser_find` call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrowserFindResultEvent {
+    pub session_id: String,
+    pub query: String,
+    pub matches: u32,
+    pub active_match: u32,
+}
+
+/// Per-session allow/deny URL pattern list, evaluated on every navigation.
+/// Patterns use `*` as a wildcard. Deny takes precedence over allow, and an
+/// empty allow-list means "allow anything that isn't denied".
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NavigationPolicy {
+    pub allow_patterns: Vec<String>,
+    pub deny_patterns: Vec<String>,
+}
+
+impl NavigationPolicy {
+    fn permits(&self, url: &str) -> bool {
+        if self.deny_patterns.iter().any(|p| pattern_matches(p, url)) {
+            return false;
+        }
+        self.allow_patterns.is_empty()
+            || self.allow_patterns.iter().any(|p| pattern_matches(p, url))
+    }
+}
+
+/// Minimal glob match (`*` = any run of characters, everything else literal)
+/// so navigation policies don't need a regex engine.
+fn pattern_matches(pattern: &str, url: &str) -> bool {
+    fn matches_here(pat: &[u8], s: &[u8]) -> bool {
+        match pat.first() {
+            None => s.is_empty(),
+            Some(b'*') => matches_here(&pat[1..], s) || (!s.is_empty() && matches_here(pat, &s[1..])),
+            Some(&c) => s.first() == Some(&c) && matches_here(&pat[1..], &s[1..]),
+        }
+    }
+    matches_here(pattern.as_bytes(), url.as_bytes())
+}
+
 /// Manages all browser webview sessions
 pub struct BrowserManager {
     sessions: HashMap<String, BrowserSession>,
+    policies: HashMap<String, NavigationPolicy>,
+    /// Labels of webviews hosting remote content. Checked by the app's
+    /// `invoke_handler` wrapper so a compromised page can never reach our
+    /// Tauri commands, the same way Tauri itself gates remote-domain IPC.
+    untrusted_webviews: HashSet<String>,
+    /// Labels of standalone windows `browser_detach_to_window` itself
+    /// created. A session's `parent_window` can also be set to an arbitrary
+    /// pre-existing window via `browser_reparent`, so "not main" alone isn't
+    /// enough to know a window is ours to close.
+    owned_windows: HashSet<String>,
 }
 
 impl BrowserManager {
     pub fn new() -> Self {
         Self {
             sessions: HashMap::new(),
+            policies: HashMap::new(),
+            untrusted_webviews: HashSet::new(),
+            owned_windows: HashSet::new(),
         }
     }
 
@@ -47,20 +116,101 @@ impl BrowserManager {
     }
 
     pub fn remove_session(&mut self, id: &str) -> Option<BrowserSession> {
-        self.sessions.remove(id)
+        self.policies.remove(id);
+        let session = self.sessions.remove(id)?;
+        self.untrusted_webviews.remove(&session.label);
+        Some(session)
     }
 
-    #[allow(dead_code)]
     pub fn list_sessions(&self) -> Vec<BrowserSession> {
         self.sessions.values().cloned().collect()
     }
 
-    #[allow(dead_code)]
     pub fn update_url(&mut self, id: &str, url: String) {
         if let Some(session) = self.sessions.get_mut(id) {
             session.current_url = url;
         }
     }
+
+    pub fn set_title(&mut self, id: &str, title: String) {
+        if let Some(session) = self.sessions.get_mut(id) {
+            session.title = title;
+        }
+    }
+
+    pub fn set_loading(&mut self, id: &str, is_loading: bool) {
+        if let Some(session) = self.sessions.get_mut(id) {
+            session.is_loading = is_loading;
+        }
+    }
+
+    pub fn set_zoom(&mut self, id: &str, zoom_level: f64) {
+        if let Some(session) = self.sessions.get_mut(id) {
+            session.zoom_level = zoom_level;
+        }
+    }
+
+    pub fn set_navigation_policy(&mut self, session_id: &str, policy: NavigationPolicy) {
+        self.policies.insert(session_id.to_string(), policy);
+    }
+
+    /// `true` unless the session has a policy that denies `url`.
+    fn navigation_allowed(&self, session_id: &str, url: &str) -> bool {
+        match self.policies.get(session_id) {
+            Some(policy) => policy.permits(url),
+            None => true,
+        }
+    }
+
+    pub fn mark_untrusted(&mut self, label: &str) {
+        self.untrusted_webviews.insert(label.to_string());
+    }
+
+    pub fn is_untrusted(&self, label: &str) -> bool {
+        self.untrusted_webviews.contains(label)
+    }
+
+    pub fn set_parent_window(&mut self, session_id: &str, window_label: String) {
+        if let Some(session) = self.sessions.get_mut(session_id) {
+            session.parent_window = window_label;
+        }
+    }
+
+    pub fn parent_window(&self, session_id: &str) -> Option<String> {
+        self.sessions
+            .get(session_id)
+            .map(|s| s.parent_window.clone())
+    }
+
+    /// Record that `window_label` is a standalone window this subsystem
+    /// created (via `browser_detach_to_window`), so it's later safe to close
+    /// automatically.
+    pub fn mark_window_owned(&mut self, window_label: String) {
+        self.owned_windows.insert(window_label);
+    }
+
+    /// `true` if `window_label` is a standalone window this subsystem itself
+    /// created, as opposed to some other window a session was reparented
+    /// into via `browser_reparent`.
+    pub fn owns_window(&self, window_label: &str) -> bool {
+        self.owned_windows.contains(window_label)
+    }
+
+    /// Stop tracking `window_label` once it's been closed.
+    pub fn forget_window(&mut self, window_label: &str) {
+        self.owned_windows.remove(window_label);
+    }
+
+    /// `true` if some session other than `excluding_session_id` is currently
+    /// parented to `window_label`. `browser_reparent` can point a second
+    /// session at a window `browser_detach_to_window` created for a first
+    /// one, so owning the window isn't enough to know it's safe to close -
+    /// it might still be hosting someone else's webview.
+    pub fn window_has_other_occupants(&self, window_label: &str, excluding_session_id: &str) -> bool {
+        self.sessions
+            .values()
+            .any(|s| s.parent_window == window_label && s.id != excluding_session_id)
+    }
 }
 
 pub type BrowserManagerState = Arc<Mutex<BrowserManager>>;
@@ -106,21 +256,56 @@ pub async fn create_browser_webview(
     let session_id_for_nav = session_id.clone();
     let session_id_for_load = session_id.clone();
     let app_for_load = app.clone();
+    let nav_state = state.inner().clone();
+    let page_state = state.inner().clone();
+    let app_for_nav = app.clone();
 
     // Create WebviewBuilder (not WebviewWindowBuilder!)
     let webview_builder = WebviewBuilder::new(&label, webview_url)
         .auto_resize()
         .on_navigation(move |nav_url| {
-            log::info!("Browser {} navigating to: {}", session_id_for_nav, nav_url);
-            true // Allow all navigation
+            let nav_url = nav_url.to_string();
+            let allowed = match nav_state.lock() {
+                Ok(mut manager) => {
+                    let allowed = manager.navigation_allowed(&session_id_for_nav, &nav_url);
+                    if allowed {
+                        manager.update_url(&session_id_for_nav, nav_url.clone());
+                    }
+                    allowed
+                }
+                Err(_) => true,
+            };
+
+            if allowed {
+                log::info!("Browser {} navigating to: {}", session_id_for_nav, nav_url);
+            } else {
+                log::warn!(
+                    "Browser {} navigation blocked by policy: {}",
+                    session_id_for_nav,
+                    nav_url
+                );
+                let _ = app_for_nav.emit(
+                    "browser-navigation-blocked",
+                    BrowserNavigationBlockedEvent {
+                        session_id: session_id_for_nav.clone(),
+                        url: nav_url,
+                    },
+                );
+            }
+
+            allowed
         })
-        .on_page_load(move |_webview: Webview, payload| {
+        .on_page_load(move |webview: Webview, payload| {
             let url = payload.url().to_string();
             let sid = session_id_for_load.clone();
 
             match payload.event() {
                 PageLoadEvent::Started => {
                     log::info!("Browser {} started loading: {}", sid, url);
+                    if let Ok(mut manager) = page_state.lock() {
+                        manager.update_url(&sid, url.clone());
+                        manager.set_loading(&sid, true);
+                    }
                     let _ = app_for_load.emit(
                         "browser-loading",
                         BrowserLoadingEvent {
@@ -131,6 +316,12 @@ pub async fn create_browser_webview(
                 }
                 PageLoadEvent::Finished => {
                     log::info!("Browser {} finished loading: {}", sid, url);
+                    let title = webview.title().unwrap_or_default();
+                    if let Ok(mut manager) = page_state.lock() {
+                        manager.update_url(&sid, url.clone());
+                        manager.set_title(&sid, title);
+                        manager.set_loading(&sid, false);
+                    }
                     // Emit URL change event to frontend
                     let _ = app_for_load.emit(
                         "browser-url-changed",
@@ -150,14 +341,31 @@ pub async fn create_browser_webview(
             }
         });
 
+    // Mark the label untrusted *before* the webview exists, not after: the
+    // remote page can start running script and firing IPC invokes the moment
+    // `add_child` returns, and `guarded_invoke_handler` in `lib.rs` only
+    // consults `is_untrusted` - if that call lands before this flag is set,
+    // it reaches the real command handler same as a trusted caller would.
+    {
+        let mut manager = state.lock().map_err(|e| format!("Lock error: {}", e))?;
+        manager.mark_untrusted(&label);
+    }
+
     // Add webview as a CHILD of the main window (truly embedded!)
-    let _webview = main_window
-        .add_child(
-            webview_builder,
-            LogicalPosition::new(x, y),
-            LogicalSize::new(width, height),
-        )
-        .map_err(|e| format!("Failed to create embedded webview: {}", e))?;
+    let add_child_result = main_window.add_child(
+        webview_builder,
+        LogicalPosition::new(x, y),
+        LogicalSize::new(width, height),
+    );
+    let _webview = match add_child_result {
+        Ok(webview) => webview,
+        Err(e) => {
+            if let Ok(mut manager) = state.lock() {
+                manager.untrusted_webviews.remove(&label);
+            }
+            return Err(format!("Failed to create embedded webview: {}", e));
+        }
+    };
 
     // Create session info
     let session = BrowserSession {
@@ -166,9 +374,10 @@ pub async fn create_browser_webview(
         current_url: initial_url.unwrap_or_default(),
         title: String::new(),
         is_loading: true,
+        parent_window: "main".to_string(),
+        zoom_level: 1.0,
     };
 
-    // Store in state
     {
         let mut manager = state.lock().map_err(|e| format!("Lock error: {}", e))?;
         manager.add_session(session.clone());
@@ -202,7 +411,8 @@ pub async fn browser_navigate(
     Ok(())
 }
 
-/// Go back in browser history
+/// Go back in browser history using the webview's native navigation, so it
+/// works even on pages whose CSP blocks injected script.
 #[tauri::command]
 pub async fn browser_go_back(app: AppHandle, session_id: String) -> Result<(), String> {
     log::info!("Browser {} going back", session_id);
@@ -213,13 +423,13 @@ pub async fn browser_go_back(app: AppHandle, session_id: String) -> Result<(), S
         .ok_or_else(|| format!("Browser {} not found", label))?;
 
     webview
-        .eval("window.history.back()")
+        .back()
         .map_err(|e| format!("Failed to go back: {}", e))?;
 
     Ok(())
 }
 
-/// Go forward in browser history
+/// Go forward in browser history using the webview's native navigation.
 #[tauri::command]
 pub async fn browser_go_forward(app: AppHandle, session_id: String) -> Result<(), String> {
     log::info!("Browser {} going forward", session_id);
@@ -230,13 +440,13 @@ pub async fn browser_go_forward(app: AppHandle, session_id: String) -> Result<()
         .ok_or_else(|| format!("Browser {} not found", label))?;
 
     webview
-        .eval("window.history.forward()")
+        .forward()
         .map_err(|e| format!("Failed to go forward: {}", e))?;
 
     Ok(())
 }
 
-/// Reload the browser page
+/// Reload the browser page using the webview's native reload.
 #[tauri::command]
 pub async fn browser_reload(app: AppHandle, session_id: String) -> Result<(), String> {
     log::info!("Browser {} reloading", session_id);
@@ -247,12 +457,57 @@ pub async fn browser_reload(app: AppHandle, session_id: String) -> Result<(), St
         .ok_or_else(|| format!("Browser {} not found", label))?;
 
     webview
-        .eval("window.location.reload()")
+        .reload()
         .map_err(|e| format!("Failed to reload: {}", e))?;
 
     Ok(())
 }
 
+/// Find text on the loaded page using the webview's native find-in-page,
+/// rather than an injected `window.find()` call (unreliable and removed on
+/// some engines). Results arrive asynchronously via `browser-find-result`.
+#[tauri::command]
+pub async fn browser_find(
+    app: AppHandle,
+    session_id: String,
+    query: String,
+    forward: bool,
+) -> Result<(), String> {
+    log::info!("Browser {} finding: {} (forward: {})", session_id, query, forward);
+
+    let label = format!("browser-{}", session_id);
+    let webview = app
+        .get_webview(&label)
+        .ok_or_else(|| format!("Browser {} not found", label))?;
+
+    let app_for_result = app.clone();
+    let sid = session_id.clone();
+    let query_for_event = query.clone();
+
+    webview
+        .find(
+            &query,
+            FindOptions {
+                forward,
+                ..Default::default()
+            },
+            move |result| {
+                let _ = app_for_result.emit(
+                    "browser-find-result",
+                    BrowserFindResultEvent {
+                        session_id: sid.clone(),
+                        query: query_for_event.clone(),
+                        matches: result.matches,
+                        active_match: result.active_match_ordinal,
+                    },
+                );
+            },
+        )
+        .map_err(|e| format!("Find failed: {}", e))?;
+
+    Ok(())
+}
+
 /// Update browser webview position and size (for embedded webviews)
 #[tauri::command]
 pub async fn update_browser_bounds(
@@ -280,6 +535,164 @@ pub async fn update_browser_bounds(
     Ok(())
 }
 
+/// Move an already-created embedded webview to become a child of a different
+/// window, preserving its navigation state. Uses the same
+/// `Webview::reparent` call as `browser_detach_to_window`/`browser_reattach_to_main`.
+#[tauri::command]
+pub async fn browser_reparent(
+    app: AppHandle,
+    state: tauri::State<'_, BrowserManagerState>,
+    session_id: String,
+    target_window_label: String,
+) -> Result<(), String> {
+    let label = format!("browser-{}", session_id);
+    let webview = app
+        .get_webview(&label)
+        .ok_or_else(|| format!("Browser {} not found", label))?;
+    let target_window = app
+        .get_window(&target_window_label)
+        .ok_or_else(|| format!("Window {} not found", target_window_label))?;
+
+    webview
+        .reparent(&target_window)
+        .map_err(|e| format!("Failed to reparent browser {}: {}", session_id, e))?;
+
+    {
+        let mut manager = state.lock().map_err(|e| format!("Lock error: {}", e))?;
+        manager.set_parent_window(&session_id, target_window_label.clone());
+    }
+
+    log::info!(
+        "Reparented browser {} to window {}",
+        session_id,
+        target_window_label
+    );
+
+    Ok(())
+}
+
+/// Tear a browser session off into its own standalone window, moving its
+/// existing webview (and navigation state) rather than recreating it.
+/// Returns the label of the new window.
+#[tauri::command]
+pub async fn browser_detach_to_window(
+    app: AppHandle,
+    state: tauri::State<'_, BrowserManagerState>,
+    session_id: String,
+    width: Option<f64>,
+    height: Option<f64>,
+) -> Result<String, String> {
+    let width = width.unwrap_or(1024.0);
+    let height = height.unwrap_or(768.0);
+
+    let label = format!("browser-{}", session_id);
+    let webview = app
+        .get_webview(&label)
+        .ok_or_else(|| format!("Browser {} not found", label))?;
+
+    let window_label = format!("browser-window-{}", session_id);
+    let detached_window = WindowBuilder::new(&app, &window_label)
+        .title(format!("Browser - {}", session_id))
+        .inner_size(width, height)
+        .build()
+        .map_err(|e| format!("Failed to create detached window: {}", e))?;
+
+    webview
+        .reparent(&detached_window)
+        .map_err(|e| format!("Failed to detach browser {}: {}", session_id, e))?;
+
+    webview
+        .set_position(LogicalPosition::new(0.0, 0.0))
+        .map_err(|e| format!("Failed to position detached browser {}: {}", session_id, e))?;
+    webview
+        .set_size(LogicalSize::new(width, height))
+        .map_err(|e| format!("Failed to size detached browser {}: {}", session_id, e))?;
+
+    {
+        let mut manager = state.lock().map_err(|e| format!("Lock error: {}", e))?;
+        manager.set_parent_window(&session_id, window_label.clone());
+        manager.mark_window_owned(window_label.clone());
+    }
+
+    log::info!(
+        "Detached browser {} into standalone window {}",
+        session_id,
+        window_label
+    );
+
+    Ok(window_label)
+}
+
+/// Re-embed a browser session back into the main window at the given bounds,
+/// closing the standalone window it was torn off into (if any).
+#[tauri::command]
+pub async fn browser_reattach_to_main(
+    app: AppHandle,
+    state: tauri::State<'_, BrowserManagerState>,
+    session_id: String,
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+) -> Result<(), String> {
+    let label = format!("browser-{}", session_id);
+    let webview = app
+        .get_webview(&label)
+        .ok_or_else(|| format!("Browser {} not found", label))?;
+
+    let previous_window = {
+        let manager = state.lock().map_err(|e| format!("Lock error: {}", e))?;
+        manager.parent_window(&session_id)
+    };
+
+    let main_window = app
+        .get_window("main")
+        .ok_or_else(|| "Main window not found".to_string())?;
+
+    webview
+        .reparent(&main_window)
+        .map_err(|e| format!("Failed to reattach browser {}: {}", session_id, e))?;
+
+    webview
+        .set_position(LogicalPosition::new(x, y))
+        .map_err(|e| format!("Failed to position browser {}: {}", session_id, e))?;
+    webview
+        .set_size(LogicalSize::new(width, height))
+        .map_err(|e| format!("Failed to size browser {}: {}", session_id, e))?;
+
+    let window_to_close = {
+        let mut manager = state.lock().map_err(|e| format!("Lock error: {}", e))?;
+        manager.set_parent_window(&session_id, "main".to_string());
+
+        // Only close the window we tore this session off into via
+        // `browser_detach_to_window` - `previous_window` could just as
+        // easily be some other window the session was reparented into via
+        // `browser_reparent`, which we have no business destroying. And even
+        // a window we own might since have picked up a second session via
+        // `browser_reparent`, so check it's not still hosting one.
+        match previous_window {
+            Some(label)
+                if manager.owns_window(&label)
+                    && !manager.window_has_other_occupants(&label, &session_id) =>
+            {
+                manager.forget_window(&label);
+                Some(label)
+            }
+            _ => None,
+        }
+    };
+
+    if let Some(label) = window_to_close {
+        if let Some(previous_window) = app.get_window(&label) {
+            let _ = previous_window.close();
+        }
+    }
+
+    log::info!("Reattached browser {} to main window", session_id);
+
+    Ok(())
+}
+
 /// Show browser webview
 #[tauri::command]
 pub async fn show_browser_webview(app: AppHandle, session_id: String) -> Result<(), String> {
@@ -311,6 +724,34 @@ pub async fn hide_browser_webview(app: AppHandle, session_id: String) -> Result<
     Ok(())
 }
 
+/// Set the allow/deny URL pattern policy evaluated by a session's
+/// `on_navigation` handler. Patterns use `*` as a wildcard; deny takes
+/// precedence over allow, and an empty allow-list means "allow anything not
+/// denied".
+#[tauri::command]
+pub async fn browser_set_navigation_policy(
+    state: tauri::State<'_, BrowserManagerState>,
+    session_id: String,
+    allow_patterns: Vec<String>,
+    deny_patterns: Vec<String>,
+) -> Result<(), String> {
+    let mut manager = state.lock().map_err(|e| format!("Lock error: {}", e))?;
+    manager.set_navigation_policy(
+        &session_id,
+        NavigationPolicy {
+            allow_patterns,
+            deny_patterns,
+        },
+    );
+
+    log::info!(
+        "Updated navigation policy for browser session {}",
+        session_id
+    );
+
+    Ok(())
+}
+
 /// Close browser webview
 #[tauri::command]
 pub async fn close_browser_webview(
@@ -325,9 +766,33 @@ pub async fn close_browser_webview(
         let _ = webview.close();
     }
 
-    {
+    let window_to_close = {
         let mut manager = state.lock().map_err(|e| format!("Lock error: {}", e))?;
-        manager.remove_session(&session_id);
+        let removed = manager.remove_session(&session_id);
+
+        // Only close the window if it's a standalone one this subsystem
+        // created via `browser_detach_to_window` - `parent_window` could
+        // just as easily be some other window the session was reparented
+        // into via `browser_reparent`, which we have no business destroying.
+        // It also might have picked up a second session via `browser_reparent`
+        // since being created, so make sure we'd not be closing out from
+        // under them too.
+        removed.and_then(|session| {
+            if manager.owns_window(&session.parent_window)
+                && !manager.window_has_other_occupants(&session.parent_window, &session_id)
+            {
+                manager.forget_window(&session.parent_window);
+                Some(session.parent_window)
+            } else {
+                None
+            }
+        })
+    };
+
+    if let Some(label) = window_to_close {
+        if let Some(window) = app.get_window(&label) {
+            let _ = window.close();
+        }
     }
 
     Ok(())
@@ -347,10 +812,64 @@ pub async fn get_browser_url(app: AppHandle, session_id: String) -> Result<Strin
     Ok(url.to_string())
 }
 
-/// Set zoom level for browser webview
+/// Return the reconciled list of browser sessions, refreshing each one
+/// against its live webview first - see `sync_browser_sessions`.
+#[tauri::command]
+pub async fn list_sessions(
+    app: AppHandle,
+    state: tauri::State<'_, BrowserManagerState>,
+) -> Result<Vec<BrowserSession>, String> {
+    sync_browser_sessions(app, state).await
+}
+
+/// Reconcile stored `BrowserSession`s with the app's actual webviews: drop
+/// sessions whose `browser-{id}` webview has gone away (e.g. the user closed
+/// a detached window), and refresh `current_url`/`title` for the ones still
+/// alive. `is_loading` is left as-is, since it's only observable through the
+/// `on_page_load` callback wired up in `create_browser_webview`.
+#[tauri::command]
+pub async fn sync_browser_sessions(
+    app: AppHandle,
+    state: tauri::State<'_, BrowserManagerState>,
+) -> Result<Vec<BrowserSession>, String> {
+    let mut manager = state.lock().map_err(|e| format!("Lock error: {}", e))?;
+
+    let stale_ids: Vec<String> = manager
+        .list_sessions()
+        .into_iter()
+        .filter(|session| app.get_webview(&session.label).is_none())
+        .map(|session| session.id)
+        .collect();
+
+    for id in &stale_ids {
+        log::info!("Pruning browser session {}: webview no longer exists", id);
+        manager.remove_session(id);
+    }
+
+    let live_ids: Vec<String> = manager.list_sessions().into_iter().map(|s| s.id).collect();
+    for id in live_ids {
+        let label = format!("browser-{}", id);
+        let Some(webview) = app.get_webview(&label) else {
+            continue;
+        };
+
+        if let Ok(url) = webview.url() {
+            manager.update_url(&id, url.to_string());
+        }
+        if let Ok(title) = webview.title() {
+            manager.set_title(&id, title);
+        }
+    }
+
+    Ok(manager.list_sessions())
+}
+
+/// Set zoom level for browser webview using the webview's native zoom, and
+/// persist it on the session so it survives navigation and reparenting.
 #[tauri::command]
 pub async fn browser_set_zoom(
     app: AppHandle,
+    state: tauri::State<'_, BrowserManagerState>,
     session_id: String,
     zoom_level: f64,
 ) -> Result<(), String> {
@@ -365,12 +884,60 @@ pub async fn browser_set_zoom(
         .get_webview(&label)
         .ok_or_else(|| format!("Browser {} not found", label))?;
 
-    // Set zoom using CSS transform for better compatibility
-    let zoom_script = format!("document.body.style.zoom = '{}';", zoom_level);
-
     webview
-        .eval(&zoom_script)
+        .set_zoom(zoom_level)
         .map_err(|e| format!("Failed to set zoom: {}", e))?;
 
+    let mut manager = state.lock().map_err(|e| format!("Lock error: {}", e))?;
+    manager.set_zoom(&session_id, zoom_level);
+
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pattern_matches_literal() {
+        assert!(pattern_matches("https://example.com/", "https://example.com/"));
+        assert!(!pattern_matches("https://example.com/", "https://example.org/"));
+    }
+
+    #[test]
+    fn pattern_matches_wildcard_suffix() {
+        assert!(pattern_matches("https://*.example.com/*", "https://docs.example.com/guide"));
+        assert!(!pattern_matches("https://*.example.com/*", "https://example.net/"));
+    }
+
+    #[test]
+    fn pattern_matches_wildcard_matches_empty_run() {
+        // `*` matches zero characters too, so an empty subdomain label qualifies.
+        assert!(pattern_matches("https://*.example.com/*", "https://.example.com/"));
+    }
+
+    #[test]
+    fn pattern_matches_requires_full_match_not_prefix() {
+        assert!(!pattern_matches("https://example.com", "https://example.com/path"));
+    }
+
+    #[test]
+    fn navigation_policy_deny_overrides_allow() {
+        let policy = NavigationPolicy {
+            allow_patterns: vec!["https://example.com/*".to_string()],
+            deny_patterns: vec!["https://example.com/admin/*".to_string()],
+        };
+        assert!(policy.permits("https://example.com/home"));
+        assert!(!policy.permits("https://example.com/admin/panel"));
+    }
+
+    #[test]
+    fn navigation_policy_empty_allow_list_permits_anything_not_denied() {
+        let policy = NavigationPolicy {
+            allow_patterns: vec![],
+            deny_patterns: vec!["https://blocked.example/*".to_string()],
+        };
+        assert!(policy.permits("https://anything.example/"));
+        assert!(!policy.permits("https://blocked.example/page"));
+    }
+}
@@ -1,4 +1,3 @@
-use std::process::Command;
 use std::sync::Arc;
 use tauri::{Manager, RunEvent};
 use tokio::sync::Mutex;
@@ -6,78 +5,28 @@ use tokio::sync::Mutex;
 mod browser;
 mod network_commands;
 mod network_monitor;
+pub mod sidecar;
 mod terminal;
 
 use browser::{
-    browser_go_back, browser_go_forward, browser_navigate, browser_reload, close_browser_webview,
-    create_browser_webview, get_browser_url, hide_browser_webview, show_browser_webview,
-    update_browser_bounds, BrowserManager, BrowserManagerState,
+    browser_detach_to_window, browser_find, browser_go_back, browser_go_forward,
+    browser_navigate, browser_reattach_to_main, browser_reload, browser_reparent,
+    browser_set_navigation_policy, browser_set_zoom, close_browser_webview,
+    create_browser_webview, get_browser_url, hide_browser_webview, list_sessions,
+    show_browser_webview, sync_browser_sessions, update_browser_bounds, BrowserManager,
+    BrowserManagerState,
 };
 use network_commands::{get_network_status, start_network_monitoring, NetworkMonitorState};
 use network_monitor::NetworkMonitor;
+use sidecar::get_sidecar_status;
 use terminal::{
-    close_all_sessions, close_session, create_local_shell, get_all_sessions,
-    launch_external_terminal, read_from_pty, rename_session, resize_pty, write_to_pty,
+    close_all_sessions, close_session, create_k8s_shell, create_local_shell,
+    create_terminal_profile, delete_terminal_profile, get_all_sessions, get_scrollback,
+    get_session_status, get_terminal_profiles, launch_external_terminal, read_from_pty,
+    rename_session, resize_pty, restore_sessions, send_signal, serialize_sessions, write_to_pty,
     TerminalManager, TerminalManagerState,
 };
 
-#[cfg(windows)]
-fn kill_process_by_port_enhanced(port: u16) {
-    log::info!("Attempting to kill process using port {} (enhanced)", port);
-
-    let netstat_output = Command::new("netstat").args(["-ano"]).output();
-
-    if let Ok(output) = netstat_output {
-        let output_str = String::from_utf8_lossy(&output.stdout);
-
-        for line in output_str.lines() {
-            if line.contains(&format!(":{}", port)) && line.contains("LISTENING") {
-                let parts: Vec<&str> = line.split_whitespace().collect();
-                if let Some(pid_str) = parts.last() {
-                    if let Ok(pid) = pid_str.parse::<u32>() {
-                        log::info!("Found process using port {}: PID {}", port, pid);
-
-                        // Try taskkill with force flag
-                        let result = Command::new("taskkill")
-                            .args(["/F", "/PID", &pid.to_string()])
-                            .output();
-
-                        match result {
-                            Ok(output) => {
-                                if output.status.success() {
-                                    log::info!(
-                                        "Successfully killed process PID {} on port {}",
-                                        pid,
-                                        port
-                                    );
-                                } else {
-                                    log::error!(
-                                        "Failed to kill process PID {} on port {}: {}",
-                                        pid,
-                                        port,
-                                        String::from_utf8_lossy(&output.stderr)
-                                    );
-                                }
-                            }
-                            Err(e) => {
-                                log::error!(
-                                    "Error executing taskkill for PID {} on port {}: {}",
-                                    pid,
-                                    port,
-                                    e
-                                );
-                            }
-                        }
-                        break;
-                    }
-                }
-            }
-        }
-    } else {
-        log::error!("Failed to execute netstat command");
-    }
-}
-
 // Initialization state for splashscreen
 #[derive(Default)]
 struct InitializationState {
@@ -124,23 +73,70 @@ async fn complete_initialization(
     Ok(())
 }
 
+/// Reject an IPC invocation if it originates from a webview `BrowserManager`
+/// has marked untrusted (i.e. one of our `browser-{session_id}` embeds
+/// hosting remote content), the same way Tauri itself gates remote-domain
+/// webviews off the IPC bridge. Wraps the real `generate_handler!` dispatch
+/// so a compromised embedded page can never reach our Kubernetes commands.
+fn guarded_invoke_handler<R: tauri::Runtime>(
+    handler: impl Fn(tauri::ipc::Invoke<R>) -> bool + Send + Sync + 'static,
+) -> impl Fn(tauri::ipc::Invoke<R>) -> bool + Send + Sync + 'static {
+    move |invoke: tauri::ipc::Invoke<R>| {
+        let label = invoke.message.webview().label().to_string();
+
+        if label.starts_with("browser-") {
+            let browser_state = invoke.message.webview().try_state::<BrowserManagerState>();
+            if let Some(browser_state) = browser_state {
+                let untrusted = browser_state
+                    .lock()
+                    .map(|manager| manager.is_untrusted(&label))
+                    .unwrap_or(false);
+
+                if untrusted {
+                    log::warn!(
+                        "Blocked IPC command '{}' from untrusted webview '{}'",
+                        invoke.message.command(),
+                        label
+                    );
+                    invoke
+                        .resolver
+                        .reject(format!("IPC is disabled for untrusted webview '{}'", label));
+                    return true;
+                }
+            }
+        }
+
+        handler(invoke)
+    }
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
-        .invoke_handler(tauri::generate_handler![
+        .invoke_handler(guarded_invoke_handler(tauri::generate_handler![
             get_network_status,
             start_network_monitoring,
             complete_initialization,
+            get_sidecar_status,
             // Terminal commands
             create_local_shell,
+            create_k8s_shell,
             write_to_pty,
             read_from_pty,
             resize_pty,
             close_session,
             get_all_sessions,
+            get_scrollback,
+            get_session_status,
+            send_signal,
+            serialize_sessions,
+            restore_sessions,
             rename_session,
             close_all_sessions,
             launch_external_terminal,
+            create_terminal_profile,
+            get_terminal_profiles,
+            delete_terminal_profile,
             // Browser commands
             create_browser_webview,
             browser_navigate,
@@ -151,8 +147,16 @@ pub fn run() {
             show_browser_webview,
             hide_browser_webview,
             close_browser_webview,
-            get_browser_url
-        ])
+            get_browser_url,
+            browser_set_navigation_policy,
+            browser_reparent,
+            browser_detach_to_window,
+            browser_reattach_to_main,
+            browser_set_zoom,
+            browser_find,
+            list_sessions,
+            sync_browser_sessions
+        ]))
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_updater::Builder::new().build())
@@ -192,6 +196,19 @@ pub fn run() {
                 std::sync::Arc::new(std::sync::Mutex::new(BrowserManager::new()));
             app.manage(browser_manager);
 
+            // Pick up the sidecar supervisor `main()` spawned before this
+            // `App` existed, so it can be queried and stopped as managed
+            // state, and so it can start emitting lifecycle events now that
+            // an `AppHandle` actually exists.
+            if let Some(supervisor) = sidecar::global_supervisor() {
+                supervisor.attach_app_handle(app.handle().clone());
+                app.manage(supervisor);
+            } else {
+                log::warn!(
+                    "No sidecar supervisor handed off from main(); sidecar status will be unavailable"
+                );
+            }
+
             // Close any leftover browser windows from previous sessions
             let app_handle = app.handle().clone();
             for (label, window) in app_handle.webview_windows() {
@@ -231,30 +248,20 @@ pub fn run() {
         })
         .build(tauri::generate_context!())
         .expect("error while building tauri application")
-        .run(|_app_handle, event| match event {
+        .run(|app_handle, event| match event {
             RunEvent::Ready => {
                 log::info!("App is ready!");
             }
             RunEvent::Exit => {
                 log::info!("App is exiting...");
 
-                // Kill processes running on ports 4688 and 4689
-                #[cfg(target_os = "windows")]
-                {
-                    log::info!("Starting Windows process cleanup...");
-                    kill_process_by_port_enhanced(4688); // operator
-                    kill_process_by_port_enhanced(4689); // orchestrator
-                }
-
-                #[cfg(any(target_os = "linux", target_os = "macos"))]
-                {
-                    log::info!("Starting Unix process cleanup...");
-                    let _ = Command::new("sh")
-                        .args(["-c", "lsof -ti:4688 | xargs -r kill -9"])
-                        .output();
-                    let _ = Command::new("sh")
-                        .args(["-c", "lsof -ti:4689 | xargs -r kill -9"])
-                        .output();
+                // Stop the exact sidecar processes we spawned, by handle -
+                // no more scanning for whatever happens to be listening on
+                // their ports.
+                if let Some(supervisor) = app_handle.try_state::<Arc<sidecar::Supervisor>>() {
+                    supervisor.stop_all();
+                } else {
+                    log::warn!("No sidecar supervisor in managed state; skipping sidecar cleanup");
                 }
 
                 log::info!("Process cleanup completed");
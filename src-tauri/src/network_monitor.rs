@@ -1,159 +1,428 @@
-use serde::{Deserialize, Serialize};
-use std::sync::{Arc, Mutex};
-use tauri::{AppHandle, Emitter};
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct NetworkStatus {
-    pub online: bool,
-}
-
-impl Default for NetworkStatus {
-    fn default() -> Self {
-        Self { online: true }
-    }
-}
-
-pub struct NetworkMonitor {
-    status: Arc<Mutex<NetworkStatus>>,
-    app_handle: AppHandle,
-}
-
-impl NetworkMonitor {
-    pub fn new(app_handle: AppHandle) -> Self {
-        Self {
-            status: Arc::new(Mutex::new(NetworkStatus::default())),
-            app_handle,
-        }
-    }
-
-    pub fn get_status(&self) -> NetworkStatus {
-        self.status.lock().unwrap().clone()
-    }
-
-    fn update_status(&self, online: bool) {
-        let mut status = self.status.lock().unwrap();
-        if status.online != online {
-            status.online = online;
-            let new_status = status.clone();
-            drop(status); // Release lock before emitting
-            
-            let _ = self.app_handle.emit("network-status-changed", &new_status);
-            log::info!("Network status changed: online={}", online);
-        }
-    }
-
-    pub async fn start_monitoring(&self) {
-        log::info!("Starting network monitoring with OS-level events...");
-        
-        // Initial check
-        let initial_status = self.check_connectivity().await;
-        self.update_status(initial_status);
-
-        // Start OS-specific monitoring
-        #[cfg(target_os = "macos")]
-        {
-            self.start_macos_monitoring().await;
-        }
-
-        #[cfg(target_os = "linux")]
-        {
-            self.start_linux_monitoring().await;
-        }
-
-        // #[cfg(target_os = "windows")]
-        // {
-        //     self.start_windows_monitoring().await;
-        // }
-    }
-
-    async fn check_connectivity(&self) -> bool {
-        // Quick connectivity check
-        match reqwest::Client::new()
-            .get("https://1.1.1.1")
-            .timeout(std::time::Duration::from_secs(3))
-            .send()
-            .await
-        {
-            Ok(_) => true,
-            Err(_) => false,
-        }
-    }
-}
-
-// macOS implementation - simplified polling approach for now
-#[cfg(target_os = "macos")]
-impl NetworkMonitor {
-    async fn start_macos_monitoring(&self) {
-        let self_clone = Arc::new(self.clone());
-        
-        tokio::spawn(async move {
-            let mut interval = tokio::time::interval(std::time::Duration::from_secs(3));
-            let mut last_status = true;
-            
-            loop {
-                interval.tick().await;
-                let current_status = self_clone.check_connectivity().await;
-                
-                if current_status != last_status {
-                    self_clone.update_status(current_status);
-                    last_status = current_status;
-                }
-            }
-        });
-    }
-}
-
-// Linux implementation using NetworkManager D-Bus
-#[cfg(target_os = "linux")]
-impl NetworkMonitor {
-    async fn start_linux_monitoring(&self) {
-        let self_clone = Arc::new(self.clone());
-        
-        tokio::spawn(async move {
-            match zbus::Connection::system().await {
-                Ok(connection) => {
-                    match zbus::proxy::ProxyBuilder::new(&connection)
-                        .interface("org.freedesktop.NetworkManager")
-                        .path("/org/freedesktop/NetworkManager")
-                        .build()
-                        .await
-                    {
-                        Ok(proxy) => {
-                            if let Ok(mut stream) = proxy.receive_signal("StateChanged").await {
-                                while let Some(signal) = stream.next().await {
-                                    if let Ok(args) = signal.body::<(u32,)>() {
-                                        let state = args.0;
-                                        // NetworkManager states: 20=DISCONNECTED, 70=CONNECTED_GLOBAL
-                                        let online = state >= 70;
-                                        self_clone.update_status(online);
-                                    }
-                                }
-                            }
-                        }
-                        Err(e) => log::error!("Failed to create NetworkManager proxy: {}", e),
-                    }
-                }
-                Err(e) => log::error!("Failed to connect to D-Bus: {}", e),
-            }
-        });
-    }
-}
-
-// Windows implementation using NetworkListManager (temporarily disabled)
-// #[cfg(target_os = "windows")]
-// impl NetworkMonitor {
-//     async fn start_windows_monitoring(&self) {
-//         // Will implement once we get the right Windows crate features
-//         log::warn!("Windows network monitoring not yet implemented");
-//     }
-// }
-
-// Implement Clone for NetworkMonitor (needed for Arc)
-impl Clone for NetworkMonitor {
-    fn clone(&self) -> Self {
-        Self {
-            status: Arc::clone(&self.status),
-            app_handle: self.app_handle.clone(),
-        }
-    }
-}
\ No newline at end of file
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct NetworkStatus {
+    pub online: bool,
+    /// Reachable, but a captive portal is intercepting requests (e.g. a hotel
+    /// wifi login page) rather than reaching the real internet.
+    pub captive_portal: bool,
+    /// Best-effort: the active connection is a metered one (cellular, or a
+    /// wifi network the user marked as metered), so large syncs should pause.
+    pub metered: bool,
+}
+
+impl Default for NetworkStatus {
+    fn default() -> Self {
+        Self {
+            online: true,
+            captive_portal: false,
+            metered: false,
+        }
+    }
+}
+
+/// Well-known endpoint that returns a bare HTTP 204 with no body on a real
+/// internet connection. A captive portal intercepts the request and responds
+/// with something else (a redirect, a login page), which is how we detect it.
+const CAPTIVE_PORTAL_PROBE_URL: &str = "http://connectivitycheck.gstatic.com/generate_204";
+
+pub struct NetworkMonitor {
+    status: Arc<Mutex<NetworkStatus>>,
+    app_handle: AppHandle,
+}
+
+impl NetworkMonitor {
+    pub fn new(app_handle: AppHandle) -> Self {
+        Self {
+            status: Arc::new(Mutex::new(NetworkStatus::default())),
+            app_handle,
+        }
+    }
+
+    pub fn get_status(&self) -> NetworkStatus {
+        *self.status.lock().unwrap()
+    }
+
+    fn update_status(&self, new_status: NetworkStatus) {
+        let mut status = self.status.lock().unwrap();
+        if *status != new_status {
+            *status = new_status;
+            drop(status); // Release lock before emitting
+
+            let _ = self.app_handle.emit("network-status-changed", &new_status);
+            log::info!(
+                "Network status changed: online={} captive_portal={} metered={}",
+                new_status.online,
+                new_status.captive_portal,
+                new_status.metered
+            );
+        }
+    }
+
+    pub async fn start_monitoring(&self) {
+        log::info!("Starting network monitoring with OS-level events...");
+
+        // Initial check
+        let initial_status = self.check_status().await;
+        self.update_status(initial_status);
+
+        // Start OS-specific monitoring
+        #[cfg(target_os = "macos")]
+        {
+            self.start_macos_monitoring().await;
+        }
+
+        #[cfg(target_os = "linux")]
+        {
+            self.start_linux_monitoring().await;
+        }
+
+        #[cfg(target_os = "windows")]
+        {
+            self.start_windows_monitoring().await;
+        }
+    }
+
+    async fn check_connectivity(&self) -> bool {
+        // Quick connectivity check
+        match reqwest::Client::new()
+            .get("https://1.1.1.1")
+            .timeout(std::time::Duration::from_secs(3))
+            .send()
+            .await
+        {
+            Ok(_) => true,
+            Err(_) => false,
+        }
+    }
+
+    /// Only meaningful once we already know we're online - an unreachable
+    /// probe means "no network", not "captive portal".
+    async fn check_captive_portal(&self) -> bool {
+        match reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .unwrap_or_default()
+            .get(CAPTIVE_PORTAL_PROBE_URL)
+            .timeout(std::time::Duration::from_secs(3))
+            .send()
+            .await
+        {
+            Ok(resp) => resp.status() != reqwest::StatusCode::NO_CONTENT,
+            Err(_) => false,
+        }
+    }
+
+    /// Combines the HTTP-based checks into a full `NetworkStatus`, reusing
+    /// `metered` detection from whichever OS backend implements it.
+    async fn check_status(&self) -> NetworkStatus {
+        let online = self.check_connectivity().await;
+        let captive_portal = if online {
+            self.check_captive_portal().await
+        } else {
+            false
+        };
+        let metered = self.check_metered().await;
+
+        NetworkStatus {
+            online,
+            captive_portal,
+            metered,
+        }
+    }
+
+    /// Like `check_status`, but reuses an `online` value already known from
+    /// an OS-level reachability/connectivity event instead of re-probing it.
+    async fn check_status_with_online(&self, online: bool) -> NetworkStatus {
+        let captive_portal = if online {
+            self.check_captive_portal().await
+        } else {
+            false
+        };
+        let metered = self.check_metered().await;
+
+        NetworkStatus {
+            online,
+            captive_portal,
+            metered,
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    async fn check_metered(&self) -> bool {
+        match zbus::Connection::system().await {
+            Ok(connection) => {
+                match zbus::proxy::ProxyBuilder::new(&connection)
+                    .interface("org.freedesktop.NetworkManager")
+                    .path("/org/freedesktop/NetworkManager")
+                    .build()
+                    .await
+                {
+                    // NetworkManager's `Metered` property already reflects the
+                    // active connection: 1 = yes, 3 = guess-yes.
+                    Ok(proxy) => match proxy.get_property::<u32>("Metered").await {
+                        Ok(value) => value == 1 || value == 3,
+                        Err(_) => false,
+                    },
+                    Err(e) => {
+                        log::warn!("Failed to query NetworkManager metered state: {}", e);
+                        false
+                    }
+                }
+            }
+            Err(_) => false,
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    async fn check_metered(&self) -> bool {
+        #[cfg(target_os = "windows")]
+        {
+            self.check_metered_windows()
+        }
+        #[cfg(not(target_os = "windows"))]
+        {
+            // No cheap, dependency-free way to ask macOS whether the active
+            // path is metered; default to "not metered" rather than guessing.
+            false
+        }
+    }
+}
+
+// macOS implementation - event-driven via SCNetworkReachability, which only
+// calls back on an actual reachability-flag transition instead of polling.
+#[cfg(target_os = "macos")]
+impl NetworkMonitor {
+    async fn start_macos_monitoring(&self) {
+        let self_clone = Arc::new(self.clone());
+        // `try_current` only succeeds on a thread the Tokio runtime itself
+        // drives; the plain `std::thread::spawn` below never qualifies, so
+        // capture the handle here, in the calling async context, instead.
+        let runtime = tokio::runtime::Handle::current();
+
+        std::thread::spawn(move || {
+            use system_configuration::core_foundation::runloop::CFRunLoop;
+            use system_configuration::network_reachability::SCNetworkReachability;
+
+            let reachability = match SCNetworkReachability::from_host("1.1.1.1") {
+                Some(r) => r,
+                None => {
+                    log::error!("Failed to create SCNetworkReachability target");
+                    return;
+                }
+            };
+
+            let callback_monitor = Arc::clone(&self_clone);
+
+            reachability.set_callback(move |flags| {
+                let monitor = Arc::clone(&callback_monitor);
+                let online = flags.contains(
+                    system_configuration::network_reachability::ReachabilityFlags::REACHABLE,
+                );
+                runtime.spawn(async move {
+                    let status = monitor.check_status_with_online(online).await;
+                    monitor.update_status(status);
+                });
+            });
+
+            if reachability
+                .schedule_with_runloop(&CFRunLoop::get_current(), unsafe {
+                    system_configuration::core_foundation::runloop::kCFRunLoopDefaultMode
+                })
+                .is_err()
+            {
+                log::error!("Failed to schedule SCNetworkReachability callback");
+                return;
+            }
+
+            // Keep this thread alive to service the run loop scheduled above.
+            CFRunLoop::run_current();
+        });
+    }
+}
+
+// Linux implementation using NetworkManager D-Bus
+#[cfg(target_os = "linux")]
+impl NetworkMonitor {
+    async fn start_linux_monitoring(&self) {
+        let self_clone = Arc::new(self.clone());
+
+        tokio::spawn(async move {
+            match zbus::Connection::system().await {
+                Ok(connection) => {
+                    match zbus::proxy::ProxyBuilder::new(&connection)
+                        .interface("org.freedesktop.NetworkManager")
+                        .path("/org/freedesktop/NetworkManager")
+                        .build()
+                        .await
+                    {
+                        Ok(proxy) => {
+                            if let Ok(mut stream) = proxy.receive_signal("StateChanged").await {
+                                while let Some(signal) = stream.next().await {
+                                    if let Ok(args) = signal.body::<(u32,)>() {
+                                        let state = args.0;
+                                        // NetworkManager states: 20=DISCONNECTED, 70=CONNECTED_GLOBAL
+                                        let online = state >= 70;
+                                        let status =
+                                            self_clone.check_status_with_online(online).await;
+                                        self_clone.update_status(status);
+                                    }
+                                }
+                            }
+                        }
+                        Err(e) => log::error!("Failed to create NetworkManager proxy: {}", e),
+                    }
+                }
+                Err(e) => log::error!("Failed to connect to D-Bus: {}", e),
+            }
+        });
+    }
+}
+
+// Windows implementation using INetworkListManager's connectivity-changed
+// connection point, so we react to real OS notifications instead of polling.
+#[cfg(target_os = "windows")]
+impl NetworkMonitor {
+    async fn start_windows_monitoring(&self) {
+        let self_clone = Arc::new(self.clone());
+        // `try_current` only succeeds on a thread the Tokio runtime itself
+        // drives; the plain `std::thread::spawn` below never qualifies, so
+        // capture the handle here, in the calling async context, instead.
+        let runtime = tokio::runtime::Handle::current();
+
+        std::thread::spawn(move || unsafe {
+            use windows::core::Interface;
+            use windows::Win32::NetworkManagement::IpHelper::{
+                GetAdaptersAddresses, GAA_FLAG_SKIP_ANYCAST,
+            };
+            use windows::Win32::Networking::NetworkListManager::{
+                INetworkListManager, INetworkListManagerEvents, INetworkListManagerEvents_Impl,
+                NetworkListManager, NLM_CONNECTIVITY,
+            };
+            use windows::Win32::System::Com::{
+                CoCreateInstance, CoInitializeEx, IConnectionPoint, IConnectionPointContainer,
+                CLSCTX_ALL, COINIT_APARTMENTTHREADED,
+            };
+            use windows::Win32::UI::WindowsAndMessaging::{
+                DispatchMessageW, GetMessageW, TranslateMessage, MSG,
+            };
+
+            #[windows::core::implement(INetworkListManagerEvents)]
+            struct ConnectivityChangedHandler {
+                monitor: Arc<NetworkMonitor>,
+                runtime: tokio::runtime::Handle,
+            }
+
+            impl INetworkListManagerEvents_Impl for ConnectivityChangedHandler {
+                fn ConnectivityChanged(
+                    &self,
+                    new_connectivity: NLM_CONNECTIVITY,
+                ) -> windows::core::Result<()> {
+                    // NLM_CONNECTIVITY_IPV4_INTERNET | NLM_CONNECTIVITY_IPV6_INTERNET
+                    let online = (new_connectivity.0 & (0x40 | 0x400)) != 0;
+                    let monitor = Arc::clone(&self.monitor);
+                    self.runtime.spawn(async move {
+                        let status = monitor.check_status_with_online(online).await;
+                        monitor.update_status(status);
+                    });
+                    Ok(())
+                }
+            }
+
+            if CoInitializeEx(None, COINIT_APARTMENTTHREADED).is_err() {
+                log::error!("Failed to initialize COM for network monitoring");
+                return;
+            }
+
+            let manager: INetworkListManager =
+                match CoCreateInstance(&NetworkListManager, None, CLSCTX_ALL) {
+                    Ok(m) => m,
+                    Err(e) => {
+                        log::error!("Failed to create INetworkListManager: {:?}", e);
+                        return;
+                    }
+                };
+
+            let container: IConnectionPointContainer = match manager.cast() {
+                Ok(c) => c,
+                Err(e) => {
+                    log::error!("Failed to get connection point container: {:?}", e);
+                    return;
+                }
+            };
+
+            let point: IConnectionPoint =
+                match container.FindConnectionPoint(&INetworkListManagerEvents::IID) {
+                    Ok(p) => p,
+                    Err(e) => {
+                        log::error!(
+                            "Failed to find INetworkListManagerEvents connection point: {:?}",
+                            e
+                        );
+                        return;
+                    }
+                };
+
+            let handler: INetworkListManagerEvents = ConnectivityChangedHandler {
+                monitor: Arc::clone(&self_clone),
+                runtime,
+            }
+            .into();
+
+            if let Err(e) = point.Advise(&handler) {
+                log::error!("Failed to subscribe to network list manager events: {:?}", e);
+                return;
+            }
+
+            let _ = GAA_FLAG_SKIP_ANYCAST; // silence unused-import when adapter enumeration isn't needed
+            let _ = GetAdaptersAddresses;
+
+            // Pump messages so COM can deliver events to this thread.
+            let mut msg = MSG::default();
+            while GetMessageW(&mut msg, None, 0, 0).as_bool() {
+                let _ = TranslateMessage(&msg);
+                DispatchMessageW(&msg);
+            }
+        });
+    }
+
+    fn check_metered_windows(&self) -> bool {
+        unsafe {
+            use windows::Win32::Networking::NetworkListManager::{
+                INetworkCostManager, NetworkListManager, NLM_CONNECTIVITY_COST_HOURLY_FEE,
+                NLM_CONNECTIVITY_COST_OVERDATALIMIT, NLM_CONNECTIVITY_COST_ROAMING,
+                NLM_CONNECTIVITY_COST_VARIABLE,
+            };
+            use windows::Win32::System::Com::{CoCreateInstance, CLSCTX_ALL};
+
+            let cost_manager: windows::core::Result<INetworkCostManager> =
+                CoCreateInstance(&NetworkListManager, None, CLSCTX_ALL);
+
+            match cost_manager {
+                Ok(manager) => match manager.GetCost(None) {
+                    Ok(cost) => {
+                        let metered_flags = NLM_CONNECTIVITY_COST_VARIABLE.0
+                            | NLM_CONNECTIVITY_COST_OVERDATALIMIT.0
+                            | NLM_CONNECTIVITY_COST_ROAMING.0
+                            | NLM_CONNECTIVITY_COST_HOURLY_FEE.0;
+                        (cost & metered_flags) != 0
+                    }
+                    Err(_) => false,
+                },
+                Err(_) => false,
+            }
+        }
+    }
+}
+
+// Implement Clone for NetworkMonitor (needed for Arc)
+impl Clone for NetworkMonitor {
+    fn clone(&self) -> Self {
+        Self {
+            status: Arc::clone(&self.status),
+            app_handle: self.app_handle.clone(),
+        }
+    }
+}
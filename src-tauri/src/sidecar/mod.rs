@@ -0,0 +1,54 @@
+//! Sidecar process lifecycle: resolving and spawning the orchestrator and
+//! operator binaries, then health-polling and restarting them if they
+//! crash.
+//!
+//! The supervisor is created in `main()`, before the Tauri `App` exists, so
+//! it's handed off through [`set_global_supervisor`] and picked up again by
+//! `run()`'s `setup` hook to become managed Tauri state.
+
+mod process;
+mod readiness;
+mod supervisor;
+mod watcher;
+
+pub use process::{get_operator_binary_path, get_orchestrator_binary_path, spawn_hidden_process};
+pub use readiness::wait_until_ready;
+pub use supervisor::{
+    RestartPolicy, SidecarDescriptor, SidecarState, SidecarStatus, Supervisor, STATUS_EVENT,
+};
+pub use watcher::{spawn_dev_watcher, WatchedBinary};
+
+use std::sync::{Arc, OnceLock};
+use tauri::State;
+
+/// Port the orchestrator sidecar listens on.
+pub const ORCHESTRATOR_PORT: u16 = 4689;
+/// Port the operator sidecar listens on.
+pub const OPERATOR_PORT: u16 = 4688;
+
+static SUPERVISOR: OnceLock<Arc<Supervisor>> = OnceLock::new();
+
+/// Hand off the `Supervisor` created in `main()` so `run()`'s `setup` hook
+/// can pick it up and `app.manage()` it. Panics if called twice - there is
+/// exactly one supervisor per process.
+pub fn set_global_supervisor(supervisor: Arc<Supervisor>) {
+    SUPERVISOR
+        .set(supervisor)
+        .unwrap_or_else(|_| panic!("sidecar supervisor already initialized"));
+}
+
+/// Retrieve the supervisor handed off from `main()`. Returns `None` if
+/// `main()` never spawned one (e.g. running under `cargo test`).
+pub fn global_supervisor() -> Option<Arc<Supervisor>> {
+    SUPERVISOR.get().cloned()
+}
+
+/// Current health snapshot of every supervised sidecar, so the splashscreen
+/// and a status indicator can show real backend health instead of a blind
+/// spinner.
+#[tauri::command]
+pub async fn get_sidecar_status(
+    supervisor: State<'_, Arc<Supervisor>>,
+) -> Result<Vec<SidecarStatus>, String> {
+    Ok(supervisor.snapshot())
+}
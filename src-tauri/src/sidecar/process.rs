@@ -0,0 +1,195 @@
+use std::process::Command;
+
+#[cfg(windows)]
+use std::os::windows::process::CommandExt;
+
+use shared_child::SharedChild;
+
+/// Resolve the orchestrator binary for the current platform/arch, matching
+/// the naming Tauri's own sidecar bundling uses for platform binaries.
+pub fn get_orchestrator_binary_path() -> String {
+    let os = std::env::consts::OS;
+    let arch = std::env::consts::ARCH;
+
+    match (os, arch) {
+        // Windows platforms
+        ("windows", "x86_64") => "bin\\orchestrator\\agentkube-orchestrator-x86_64-pc-windows-msvc.exe".to_string(),
+        ("windows", "x86") => "bin\\orchestrator\\agentkube-orchestrator-i686-pc-windows-msvc.exe".to_string(),
+        ("windows", "aarch64") => "bin\\orchestrator\\agentkube-orchestrator-aarch64-pc-windows-msvc.exe".to_string(),
+
+        // macOS platforms
+        ("macos", "x86_64") => "/Applications/Agentkube.app/Contents/Resources/bin/orchestrator/agentkube-orchestrator-x86_64-apple-darwin".to_string(),
+        ("macos", "aarch64") => "/Applications/Agentkube.app/Contents/Resources/bin/orchestrator/agentkube-orchestrator-aarch64-apple-darwin".to_string(),
+
+        // Linux platforms
+        ("linux", "x86_64") => "bin/orchestrator/agentkube-orchestrator-x86_64-unknown-linux-gnu".to_string(),
+        ("linux", "aarch64") => "bin/orchestrator/agentkube-orchestrator-aarch64-unknown-linux-gnu".to_string(),
+
+        // Fallback
+        _ => {
+            log::warn!("Unsupported platform: {}-{}, using fallback binary path", os, arch);
+            if os == "windows" {
+                "bin\\orchestrator\\orchestrator.exe".to_string()
+            } else {
+                "bin/orchestrator/orchestrator".to_string()
+            }
+        }
+    }
+}
+
+/// Resolve the operator binary for the current platform/arch, matching the
+/// naming Tauri's own sidecar bundling uses for platform binaries.
+pub fn get_operator_binary_path() -> String {
+    let os = std::env::consts::OS;
+    let arch = std::env::consts::ARCH;
+
+    match (os, arch) {
+        // Windows platforms
+        ("windows", "x86_64") => "bin\\operator\\agentkube-operator-x86_64-pc-windows-msvc.exe".to_string(),
+        ("windows", "x86") => "bin\\operator\\agentkube-operator-i686-pc-windows-msvc.exe".to_string(),
+        ("windows", "aarch64") => "bin\\operator\\agentkube-operator-aarch64-pc-windows-msvc.exe".to_string(),
+
+        // macOS platforms
+        ("macos", "x86_64") => "/Applications/Agentkube.app/Contents/Resources/bin/operator/agentkube-operator-x86_64-apple-darwin".to_string(),
+        ("macos", "aarch64") => "/Applications/Agentkube.app/Contents/Resources/bin/operator/agentkube-operator-aarch64-apple-darwin".to_string(),
+
+        // Linux platforms
+        ("linux", "x86_64") => "bin/operator/agentkube-operator-x86_64-unknown-linux-gnu".to_string(),
+        ("linux", "aarch64") => "bin/operator/agentkube-operator-aarch64-unknown-linux-gnu".to_string(),
+
+        // Fallback
+        _ => {
+            log::warn!("Unsupported platform: {}-{}, using fallback binary path", os, arch);
+            if os == "windows" {
+                "bin\\operator\\operator.exe".to_string()
+            } else {
+                "bin/operator/operator".to_string()
+            }
+        }
+    }
+}
+
+fn get_log_directory() -> std::path::PathBuf {
+    // Get platform-specific log directory that matches Tauri's location
+    if cfg!(target_os = "macos") {
+        dirs::home_dir()
+            .unwrap_or_else(|| std::path::PathBuf::from("."))
+            .join("Library")
+            .join("Logs")
+            .join("platform.agentkube.app")
+    } else if cfg!(target_os = "windows") {
+        dirs::config_dir()
+            .unwrap_or_else(|| std::path::PathBuf::from("."))
+            .join("platform.agentkube.app")
+            .join("logs")
+    } else {
+        // Linux
+        dirs::config_dir()
+            .unwrap_or_else(|| std::path::PathBuf::from("."))
+            .join("platform.agentkube.app")
+            .join("logs")
+    }
+}
+
+fn get_comprehensive_path() -> String {
+    // Common PATH locations on macOS
+    let mut path_candidates = vec![
+        "/usr/local/bin".to_string(),
+        "/opt/homebrew/bin".to_string(),
+        "/usr/bin".to_string(),
+        "/bin".to_string(),
+        "/usr/sbin".to_string(),
+        "/sbin".to_string(),
+        "/usr/local/sbin".to_string(),
+        "/opt/homebrew/sbin".to_string(),
+        "/usr/local/go/bin".to_string(),
+    ];
+
+    // Add user-specific paths if HOME is available
+    if let Ok(home) = std::env::var("HOME") {
+        path_candidates.push(format!("{}/go/bin", home));
+        path_candidates.push(format!("{}/.cargo/bin", home));
+        path_candidates.push(format!("{}/.local/bin", home));
+        path_candidates.push(format!("{}/bin", home));
+        path_candidates.push(format!("{}/.npm-global/bin", home));
+    }
+
+    // Get existing PATH and split it
+    let existing_path = std::env::var("PATH").unwrap_or_default();
+    let mut all_paths = Vec::new();
+
+    // Add existing PATH entries first
+    if !existing_path.is_empty() {
+        all_paths.extend(existing_path.split(':').map(|s| s.to_string()));
+    }
+
+    // Add our candidates that actually exist
+    for path in &path_candidates {
+        if std::path::Path::new(path).exists() && !all_paths.contains(path) {
+            all_paths.push(path.clone());
+        }
+    }
+
+    all_paths.join(":")
+}
+
+/// Spawn `binary_path` with stdout/stderr redirected to `<log_name>.log` /
+/// `<log_name>-error.log` under the platform log directory, and with a PATH
+/// assembled from common install locations so a GUI-launched sidecar (which
+/// doesn't inherit a login shell's PATH) can still find `kubectl`, `docker`,
+/// etc.
+///
+/// Returns a `SharedChild` rather than a plain `std::process::Child` so the
+/// handle can be `wait()`/`kill()`-ed from the supervisor's polling thread
+/// and the Tauri event loop at the same time, without a `&mut` handle
+/// bouncing between them.
+pub fn spawn_hidden_process(binary_path: &str, log_name: &str) -> Result<SharedChild, std::io::Error> {
+    let mut cmd = Command::new(binary_path);
+
+    // Create log directory if it doesn't exist
+    let log_dir = get_log_directory();
+    if let Err(e) = std::fs::create_dir_all(&log_dir) {
+        log::warn!("Failed to create log directory: {}", e);
+    }
+
+    // Set up log files for stdout and stderr
+    let stdout_log = log_dir.join(format!("{}.log", log_name));
+    let stderr_log = log_dir.join(format!("{}-error.log", log_name));
+
+    let stdout_file = std::fs::File::create(&stdout_log)?;
+    let stderr_file = std::fs::File::create(&stderr_log)?;
+
+    cmd.stdout(stdout_file);
+    cmd.stderr(stderr_file);
+
+    // Set comprehensive PATH environment
+    let comprehensive_path = get_comprehensive_path();
+    cmd.env("PATH", &comprehensive_path);
+
+    // Set other essential environment variables
+    if let Ok(home) = std::env::var("HOME") {
+        cmd.env("HOME", home);
+    }
+    if let Ok(user) = std::env::var("USER") {
+        cmd.env("USER", user);
+    }
+    if let Ok(shell) = std::env::var("SHELL") {
+        cmd.env("SHELL", shell);
+    }
+
+    log::info!(
+        "Binary logs will be written to: {} and {}",
+        stdout_log.display(),
+        stderr_log.display()
+    );
+    log::info!("Setting comprehensive PATH for {}: {}", log_name, comprehensive_path);
+
+    #[cfg(windows)]
+    {
+        // On Windows, use CREATE_NO_WINDOW flag to hide console windows
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    SharedChild::spawn(&mut cmd)
+}
@@ -0,0 +1,51 @@
+use std::net::TcpStream;
+use std::time::{Duration, Instant};
+
+const RETRY_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Poll `127.0.0.1:<port>` with short `TcpStream::connect_timeout` attempts
+/// until one succeeds or `timeout` elapses, instead of guessing a fixed
+/// startup delay. Returns whether the port came up in time.
+pub fn wait_until_ready(port: u16, timeout: Duration) -> bool {
+    let deadline = Instant::now() + timeout;
+    let addr = format!("127.0.0.1:{}", port)
+        .parse()
+        .expect("127.0.0.1:<port> is always a valid socket address");
+
+    loop {
+        if TcpStream::connect_timeout(&addr, RETRY_INTERVAL).is_ok() {
+            return true;
+        }
+
+        if Instant::now() >= deadline {
+            return false;
+        }
+
+        std::thread::sleep(RETRY_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    #[test]
+    fn ready_port_returns_true_immediately() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind ephemeral port");
+        let port = listener.local_addr().unwrap().port();
+
+        assert!(wait_until_ready(port, Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn closed_port_times_out() {
+        // Bind then drop to get a port nothing is listening on.
+        let port = {
+            let listener = TcpListener::bind("127.0.0.1:0").expect("bind ephemeral port");
+            listener.local_addr().unwrap().port()
+        };
+
+        assert!(!wait_until_ready(port, Duration::from_millis(50)));
+    }
+}
@@ -0,0 +1,591 @@
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use shared_child::SharedChild;
+use tauri::{AppHandle, Emitter};
+
+use super::process::spawn_hidden_process;
+use super::readiness::wait_until_ready;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+pub(crate) const SHUTDOWN_GRACE: Duration = Duration::from_secs(5);
+const SHUTDOWN_POLL_INTERVAL: Duration = Duration::from_millis(100);
+const READY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Event the webview can subscribe to for sidecar lifecycle transitions;
+/// payload is a [`SidecarStatus`].
+pub const STATUS_EVENT: &str = "sidecar://status";
+
+/// Backoff and circuit-breaker knobs for restarting a crashed sidecar.
+#[derive(Clone, Copy, Debug)]
+pub struct RestartPolicy {
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub max_restarts: u32,
+    pub window: Duration,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(30),
+            max_restarts: 5,
+            window: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Everything the supervisor needs to (re)spawn one sidecar. `path_fn` is a
+/// plain fn pointer rather than a closure so descriptors stay `'static` and
+/// cheap to hand to a background thread. `ready_port`, if set, is probed
+/// with a TCP connect after every (re)spawn to tell "starting" from "ready".
+pub struct SidecarDescriptor {
+    pub name: &'static str,
+    pub path_fn: fn() -> String,
+    pub log_name: &'static str,
+    pub ready_port: Option<u16>,
+    pub restart_policy: RestartPolicy,
+}
+
+/// A lifecycle transition for one supervised sidecar.
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SidecarState {
+    Starting,
+    Ready,
+    Crashed,
+    Restarting,
+    Stopped,
+}
+
+/// Point-in-time health snapshot for one supervised sidecar; also the
+/// payload emitted on [`STATUS_EVENT`].
+#[derive(Clone, Debug, Serialize)]
+pub struct SidecarStatus {
+    pub name: &'static str,
+    pub state: SidecarState,
+    pub pid: Option<u32>,
+    pub restart_count: u32,
+    pub last_exit: Option<String>,
+    pub circuit_broken: bool,
+}
+
+struct Managed {
+    descriptor: SidecarDescriptor,
+    // `SharedChild::kill`/`wait`/`try_wait` take `&self`, so the Mutex here
+    // only guards *which* child is current, never blocks a caller wanting
+    // to signal the one that's already there.
+    child: Mutex<Option<Arc<SharedChild>>>,
+    status: Mutex<SidecarStatus>,
+    app_handle: Arc<Mutex<Option<AppHandle>>>,
+    // Set by `Supervisor::restart` just before it kills the child, so
+    // `supervise` can tell a deliberate restart (e.g. the dev watcher
+    // picking up a rebuilt binary) from a real crash and respawn it
+    // immediately without charging it against the restart-policy circuit
+    // breaker.
+    restart_requested: std::sync::atomic::AtomicBool,
+    // Set by `Supervisor::stop_all` before it takes the child, so
+    // `supervise` can tell "we tore this down on purpose, stop polling"
+    // from "the child slot is empty because a respawn attempt failed" -
+    // the two look identical from `child == None` alone.
+    shutting_down: std::sync::atomic::AtomicBool,
+}
+
+impl Managed {
+    /// Emit the current status snapshot on [`STATUS_EVENT`] if an
+    /// `AppHandle` has been attached yet (it isn't during the window
+    /// between `main()` spawning sidecars and `run()`'s `setup` hook).
+    fn emit_status(&self) {
+        let Some(app_handle) = self.app_handle.lock().unwrap().clone() else {
+            return;
+        };
+        let status = self.status.lock().unwrap().clone();
+        if let Err(e) = app_handle.emit(STATUS_EVENT, status) {
+            log::warn!("Failed to emit {} for {}: {}", STATUS_EVENT, self.descriptor.name, e);
+        }
+    }
+
+    /// Wait for `ready_port` (if any) to accept connections, updating and
+    /// emitting status as "ready" on success. Logs and leaves the status as
+    /// "starting" on timeout - the sidecar may still come up later.
+    fn wait_and_emit_ready(&self) {
+        let Some(port) = self.descriptor.ready_port else {
+            return;
+        };
+        let started = Instant::now();
+        if wait_until_ready(port, READY_TIMEOUT) {
+            log::info!("{} ready after {:?}", self.descriptor.name, started.elapsed());
+            self.status.lock().unwrap().state = SidecarState::Ready;
+        } else {
+            log::warn!(
+                "{} did not become ready on port {} within {:?}",
+                self.descriptor.name,
+                port,
+                READY_TIMEOUT
+            );
+        }
+        self.emit_status();
+    }
+}
+
+/// Owns every supervised sidecar's handle (this is the sidecar lifecycle's
+/// managed Tauri state) and the background thread that health-polls it. The
+/// invariant each thread upholds is that exactly one live child exists per
+/// descriptor at a time: an unexpected exit is always replaced (up to the
+/// restart policy's circuit breaker) before the thread polls again.
+#[derive(Default)]
+pub struct Supervisor {
+    managed: Mutex<Vec<Arc<Managed>>>,
+    app_handle: Arc<Mutex<Option<AppHandle>>>,
+}
+
+impl Supervisor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Attach the `AppHandle` once the Tauri `App` exists, so transitions
+    /// from this point on emit [`STATUS_EVENT`]. Transitions before this
+    /// point were not emitted and are not replayed; [`Supervisor::snapshot`]
+    /// still reports their resulting state. Safe to call from `setup`.
+    pub fn attach_app_handle(&self, app_handle: AppHandle) {
+        *self.app_handle.lock().unwrap() = Some(app_handle);
+    }
+
+    /// Spawn `descriptor`'s process and start its supervising thread.
+    pub fn spawn(&self, descriptor: SidecarDescriptor) {
+        let path = (descriptor.path_fn)();
+        let child = match spawn_hidden_process(&path, descriptor.log_name) {
+            Ok(child) => {
+                log::info!("{} started with PID: {}", descriptor.name, child.id());
+                Some(Arc::new(child))
+            }
+            Err(e) => {
+                log::error!("Failed to start {}: {}", descriptor.name, e);
+                None
+            }
+        };
+
+        let status = SidecarStatus {
+            name: descriptor.name,
+            state: if child.is_some() { SidecarState::Starting } else { SidecarState::Crashed },
+            pid: child.as_deref().map(SharedChild::id),
+            restart_count: 0,
+            last_exit: None,
+            circuit_broken: false,
+        };
+
+        let managed = Arc::new(Managed {
+            descriptor,
+            child: Mutex::new(child),
+            status: Mutex::new(status),
+            app_handle: self.app_handle.clone(),
+            restart_requested: std::sync::atomic::AtomicBool::new(false),
+            shutting_down: std::sync::atomic::AtomicBool::new(false),
+        });
+
+        self.managed.lock().unwrap().push(managed.clone());
+        managed.emit_status();
+        managed.wait_and_emit_ready();
+        thread::spawn(move || supervise(managed));
+    }
+
+    /// Current health snapshot for `name`, or `None` if no such sidecar was
+    /// ever spawned.
+    pub fn status(&self, name: &str) -> Option<SidecarStatus> {
+        self.managed
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|managed| managed.descriptor.name == name)
+            .map(|managed| managed.status.lock().unwrap().clone())
+    }
+
+    /// Snapshot of every supervised sidecar, for `get_sidecar_status`.
+    pub fn snapshot(&self) -> Vec<SidecarStatus> {
+        self.managed
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|managed| managed.status.lock().unwrap().clone())
+            .collect()
+    }
+
+    /// Flag `name`'s current child as a deliberate restart and kill it, so
+    /// the supervising thread respawns it right away without counting it
+    /// against the restart policy's circuit breaker. Backs a future
+    /// `restart_sidecar` command - the supervising thread stays the only
+    /// place that spawns a replacement.
+    pub fn restart(&self, name: &str) -> bool {
+        let Some(managed) = self
+            .managed
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|managed| managed.descriptor.name == name)
+            .cloned()
+        else {
+            return false;
+        };
+
+        let Some(child) = managed.child.lock().unwrap().clone() else {
+            return false;
+        };
+
+        managed
+            .restart_requested
+            .store(true, std::sync::atomic::Ordering::SeqCst);
+        if let Err(e) = child.kill() {
+            log::warn!("Failed to kill {} for restart: {}", name, e);
+            return false;
+        }
+        true
+    }
+
+    /// Gracefully stop every supervised child (SIGTERM, poll, SIGKILL) by
+    /// the exact handle we spawned it with, e.g. on `RunEvent::Exit`.
+    pub fn stop_all(&self) {
+        for managed in self.managed.lock().unwrap().iter() {
+            managed
+                .shutting_down
+                .store(true, std::sync::atomic::Ordering::SeqCst);
+            let child = managed.child.lock().unwrap().take();
+            if let Some(child) = child {
+                shutdown_child(&child, managed.descriptor.name, SHUTDOWN_GRACE);
+                managed.status.lock().unwrap().state = SidecarState::Stopped;
+                managed.emit_status();
+            }
+        }
+    }
+}
+
+/// Poll `managed`'s child on a fixed interval via `try_wait` (never a
+/// blocking `wait`, so a wedged sidecar can't hang this thread). On an
+/// unexpected exit, respawn it with exponential backoff, resetting the
+/// backoff and restart count at the first crash after
+/// `restart_policy.window` has elapsed since the current window began; if `restart_policy.max_restarts` is hit inside the
+/// window, the circuit trips and the sidecar is left dead rather than
+/// looping forever.
+fn supervise(managed: Arc<Managed>) {
+    let mut backoff = managed.descriptor.restart_policy.initial_backoff;
+    let mut window_start = Instant::now();
+    let mut restarts_in_window = 0u32;
+
+    loop {
+        thread::sleep(POLL_INTERVAL);
+
+        let current = managed.child.lock().unwrap().clone();
+        let Some(current) = current else {
+            if managed
+                .shutting_down
+                .load(std::sync::atomic::Ordering::SeqCst)
+            {
+                // `stop_all` took the child and isn't bringing it back.
+                return;
+            }
+            // No child, but nobody asked us to stop - a previous respawn
+            // attempt below must have failed, leaving the slot empty.
+            // Retry it on the same backoff/circuit-breaker schedule a
+            // crash gets, rather than mistaking "child absent" for
+            // "deliberately stopped" and abandoning supervision outright.
+            if !retry_failed_respawn(&managed, &mut backoff, &mut window_start, &mut restarts_in_window) {
+                return;
+            }
+            continue;
+        };
+
+        let exit_status = match current.try_wait() {
+            Ok(Some(status)) => {
+                let mut child_guard = managed.child.lock().unwrap();
+                // Only clear the slot if it still holds this child (e.g.
+                // `stop_all` may already have taken it).
+                if matches!(child_guard.as_ref(), Some(c) if Arc::ptr_eq(c, &current)) {
+                    *child_guard = None;
+                }
+                Some(status)
+            }
+            Ok(None) => None,
+            Err(e) => {
+                log::warn!("Failed to poll {}: {}", managed.descriptor.name, e);
+                None
+            }
+        };
+
+        let Some(exit_status) = exit_status else {
+            continue;
+        };
+
+        let deliberate = managed
+            .restart_requested
+            .swap(false, std::sync::atomic::Ordering::SeqCst);
+
+        if deliberate {
+            // e.g. the dev watcher restarting a rebuilt binary: respawn
+            // right away, and don't charge it against the crash-recovery
+            // circuit breaker below.
+            log::info!("{} stopped for a deliberate restart", managed.descriptor.name);
+            {
+                let mut status = managed.status.lock().unwrap();
+                status.state = SidecarState::Restarting;
+                status.pid = None;
+                status.last_exit = Some(exit_status.to_string());
+            }
+            managed.emit_status();
+
+            let path = (managed.descriptor.path_fn)();
+            match spawn_hidden_process(&path, managed.descriptor.log_name) {
+                Ok(child) => {
+                    log::info!("{} restarted with PID: {}", managed.descriptor.name, child.id());
+                    {
+                        let mut status = managed.status.lock().unwrap();
+                        status.state = SidecarState::Starting;
+                        status.pid = Some(child.id());
+                    }
+                    *managed.child.lock().unwrap() = Some(Arc::new(child));
+                    managed.emit_status();
+                    managed.wait_and_emit_ready();
+                }
+                Err(e) => {
+                    log::error!("Failed to restart {}: {}", managed.descriptor.name, e);
+                }
+            }
+            continue;
+        }
+
+        log::warn!("{} exited unexpectedly: {}", managed.descriptor.name, exit_status);
+        {
+            let mut status = managed.status.lock().unwrap();
+            status.state = SidecarState::Crashed;
+            status.pid = None;
+            status.last_exit = Some(exit_status.to_string());
+        }
+        managed.emit_status();
+
+        if window_start.elapsed() > managed.descriptor.restart_policy.window {
+            window_start = Instant::now();
+            restarts_in_window = 0;
+            backoff = managed.descriptor.restart_policy.initial_backoff;
+        }
+
+        if restarts_in_window >= managed.descriptor.restart_policy.max_restarts {
+            log::error!(
+                "{} crashed {} times within {:?}, giving up (circuit breaker tripped)",
+                managed.descriptor.name,
+                restarts_in_window,
+                managed.descriptor.restart_policy.window
+            );
+            managed.status.lock().unwrap().circuit_broken = true;
+            managed.emit_status();
+            return;
+        }
+
+        log::info!(
+            "Restarting {} in {:?} (attempt {}/{})",
+            managed.descriptor.name,
+            backoff,
+            restarts_in_window + 1,
+            managed.descriptor.restart_policy.max_restarts
+        );
+        managed.status.lock().unwrap().state = SidecarState::Restarting;
+        managed.emit_status();
+        thread::sleep(backoff);
+
+        let path = (managed.descriptor.path_fn)();
+        match spawn_hidden_process(&path, managed.descriptor.log_name) {
+            Ok(child) => {
+                log::info!("{} restarted with PID: {}", managed.descriptor.name, child.id());
+                {
+                    let mut status = managed.status.lock().unwrap();
+                    status.state = SidecarState::Starting;
+                    status.pid = Some(child.id());
+                    status.restart_count += 1;
+                }
+                *managed.child.lock().unwrap() = Some(Arc::new(child));
+                managed.emit_status();
+                managed.wait_and_emit_ready();
+            }
+            Err(e) => {
+                log::error!("Failed to restart {}: {}", managed.descriptor.name, e);
+            }
+        }
+
+        restarts_in_window += 1;
+        backoff = (backoff * 2).min(managed.descriptor.restart_policy.max_backoff);
+    }
+}
+
+/// Re-attempt spawning `managed`'s process after an earlier respawn came up
+/// empty-handed, following the same backoff/circuit-breaker bookkeeping a
+/// crash respawn gets. Returns `false` once the circuit trips, telling
+/// `supervise` to stop polling.
+fn retry_failed_respawn(
+    managed: &Managed,
+    backoff: &mut Duration,
+    window_start: &mut Instant,
+    restarts_in_window: &mut u32,
+) -> bool {
+    if window_start.elapsed() > managed.descriptor.restart_policy.window {
+        *window_start = Instant::now();
+        *restarts_in_window = 0;
+        *backoff = managed.descriptor.restart_policy.initial_backoff;
+    }
+
+    if *restarts_in_window >= managed.descriptor.restart_policy.max_restarts {
+        log::error!(
+            "{} failed to restart {} times within {:?}, giving up (circuit breaker tripped)",
+            managed.descriptor.name,
+            *restarts_in_window,
+            managed.descriptor.restart_policy.window
+        );
+        managed.status.lock().unwrap().circuit_broken = true;
+        managed.emit_status();
+        return false;
+    }
+
+    log::info!(
+        "Retrying {} restart in {:?} (attempt {}/{})",
+        managed.descriptor.name,
+        *backoff,
+        *restarts_in_window + 1,
+        managed.descriptor.restart_policy.max_restarts
+    );
+    thread::sleep(*backoff);
+
+    // `stop_all` may have run while we were asleep - don't spawn a
+    // replacement process nobody will ever reap.
+    if managed
+        .shutting_down
+        .load(std::sync::atomic::Ordering::SeqCst)
+    {
+        return false;
+    }
+
+    let path = (managed.descriptor.path_fn)();
+    match spawn_hidden_process(&path, managed.descriptor.log_name) {
+        Ok(child) => {
+            log::info!("{} restarted with PID: {}", managed.descriptor.name, child.id());
+            {
+                let mut status = managed.status.lock().unwrap();
+                status.state = SidecarState::Starting;
+                status.pid = Some(child.id());
+                status.restart_count += 1;
+            }
+            *managed.child.lock().unwrap() = Some(Arc::new(child));
+            managed.emit_status();
+            managed.wait_and_emit_ready();
+        }
+        Err(e) => {
+            log::error!("Failed to restart {}: {}", managed.descriptor.name, e);
+        }
+    }
+
+    *restarts_in_window += 1;
+    *backoff = (*backoff * 2).min(managed.descriptor.restart_policy.max_backoff);
+    true
+}
+
+/// Ask `child` to exit gracefully and wait up to `grace` for it to do so,
+/// polling `try_wait` rather than blocking on `wait` so a wedged sidecar
+/// can't hang the caller. Escalates to `child.kill()` (SIGKILL) only if the
+/// process is still alive once the grace period elapses.
+fn shutdown_child(child: &SharedChild, name: &str, grace: Duration) {
+    #[cfg(unix)]
+    {
+        let pid = child.id() as libc::pid_t;
+        let result = unsafe { libc::kill(pid, libc::SIGTERM) };
+        if result != 0 {
+            log::warn!(
+                "Failed to send SIGTERM to {} (pid {}): {}",
+                name,
+                pid,
+                std::io::Error::last_os_error()
+            );
+        } else {
+            log::info!("Sent SIGTERM to {} (pid {})", name, pid);
+        }
+    }
+
+    #[cfg(windows)]
+    {
+        let pid = child.id();
+        let result = std::process::Command::new("taskkill")
+            .args(["/PID", &pid.to_string()])
+            .output();
+        match result {
+            Ok(output) if output.status.success() => {
+                log::info!("Sent graceful taskkill to {} (pid {})", name, pid)
+            }
+            Ok(output) => log::warn!(
+                "Graceful taskkill for {} (pid {}) did not succeed: {}",
+                name,
+                pid,
+                String::from_utf8_lossy(&output.stderr)
+            ),
+            Err(e) => log::warn!("Failed to run taskkill for {} (pid {}): {}", name, pid, e),
+        }
+    }
+
+    let deadline = Instant::now() + grace;
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => {
+                log::info!("{} exited gracefully: {}", name, status);
+                return;
+            }
+            Ok(None) => {
+                if Instant::now() >= deadline {
+                    break;
+                }
+                thread::sleep(SHUTDOWN_POLL_INTERVAL);
+            }
+            Err(e) => {
+                log::warn!("Failed to poll {} exit status: {}", name, e);
+                break;
+            }
+        }
+    }
+
+    log::warn!("{} did not exit within {:?}, escalating to SIGKILL", name, grace);
+    if let Err(e) = child.kill() {
+        log::error!("Failed to kill {}: {}", name, e);
+    } else {
+        let _ = child.wait();
+        log::info!("{} force-killed", name);
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn spawn_sh(script: &str) -> SharedChild {
+        let mut cmd = std::process::Command::new("sh");
+        cmd.arg("-c").arg(script);
+        SharedChild::spawn(&mut cmd).expect("spawn sh")
+    }
+
+    #[test]
+    fn shutdown_child_reports_a_graceful_exit() {
+        // No trap: the default action for SIGTERM is immediate termination.
+        let child = spawn_sh("sleep 100");
+        shutdown_child(&child, "test", Duration::from_secs(5));
+
+        let status = child.try_wait().expect("try_wait after shutdown");
+        assert!(status.is_some(), "child should have exited");
+    }
+
+    #[test]
+    fn shutdown_child_escalates_to_sigkill_if_term_is_ignored() {
+        let child = spawn_sh("trap '' TERM; sleep 100");
+        shutdown_child(&child, "test", Duration::from_millis(300));
+
+        let status = child.try_wait().expect("try_wait after shutdown");
+        assert!(
+            status.is_some(),
+            "child ignoring SIGTERM should still be gone after SIGKILL escalation"
+        );
+    }
+}
@@ -0,0 +1,103 @@
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::mpsc::channel;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use notify::{RecursiveMode, Watcher};
+
+use super::supervisor::Supervisor;
+
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// A sidecar binary this dev watcher should hot-restart when it changes.
+pub struct WatchedBinary {
+    pub sidecar_name: &'static str,
+    pub binary_path: PathBuf,
+}
+
+/// Spawn a background thread that watches each `WatchedBinary`'s path and,
+/// on a debounced change, restarts its sidecar through `Supervisor::restart`,
+/// which only kills the child and leaves the respawn to the supervising
+/// thread, so a rebuild-triggered restart can never race with the
+/// supervisor's own auto-restart logic.
+///
+/// Opt-in only: build tools write binaries in multiple steps (write, then
+/// rename, then chmod), so events are coalesced within [`DEBOUNCE`] into a
+/// single restart. Callers should gate this on `cfg!(debug_assertions)` or
+/// an explicit `--watch` flag; it has no business running in production.
+pub fn spawn_dev_watcher(supervisor: Arc<Supervisor>, binaries: Vec<WatchedBinary>) {
+    let (tx, rx) = channel();
+
+    let mut watcher = match notify::recommended_watcher(tx) {
+        Ok(watcher) => watcher,
+        Err(e) => {
+            log::warn!("Failed to create dev sidecar watcher: {}", e);
+            return;
+        }
+    };
+
+    for binary in &binaries {
+        let Some(parent) = binary.binary_path.parent() else {
+            continue;
+        };
+        if let Err(e) = watcher.watch(parent, RecursiveMode::NonRecursive) {
+            log::warn!(
+                "Failed to watch {} for {}: {}",
+                parent.display(),
+                binary.sidecar_name,
+                e
+            );
+        }
+    }
+
+    log::info!("Dev sidecar watcher active for {} binaries", binaries.len());
+
+    thread::spawn(move || {
+        // Keep the watcher alive for as long as this thread runs; dropping
+        // it would stop the underlying OS notifications.
+        let _watcher = watcher;
+        let mut pending = HashSet::new();
+
+        loop {
+            let Ok(event) = rx.recv() else {
+                return;
+            };
+            collect_matches(&event, &binaries, &mut pending);
+
+            // Drain anything else that arrives within the debounce window so
+            // a multi-step build triggers exactly one restart.
+            loop {
+                match rx.recv_timeout(DEBOUNCE) {
+                    Ok(event) => collect_matches(&event, &binaries, &mut pending),
+                    Err(_) => break,
+                }
+            }
+
+            for name in pending.drain() {
+                log::info!("{} binary changed, hot-restarting", name);
+                if !supervisor.restart(name) {
+                    log::warn!("Hot-restart of {} did not find a running process", name);
+                }
+            }
+        }
+    });
+}
+
+fn collect_matches(
+    event: &notify::Result<notify::Event>,
+    binaries: &[WatchedBinary],
+    pending: &mut HashSet<&'static str>,
+) {
+    let Ok(event) = event else {
+        return;
+    };
+    for changed in &event.paths {
+        for binary in binaries {
+            if changed == &binary.binary_path {
+                pending.insert(binary.sidecar_name);
+            }
+        }
+    }
+}
@@ -1,10 +1,15 @@
+mod osc;
+mod scrollback;
+
+use osc::{OscEvent, OscScanner};
 use portable_pty::{CommandBuilder, NativePtySystem, PtyPair, PtySize, PtySystem};
+use scrollback::Scrollback;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::io::{Read, Write};
 use std::sync::{mpsc, Arc, Mutex};
 use std::thread;
-use tauri::State;
+use tauri::{AppHandle, Emitter, Manager, State};
 use uuid::Uuid;
 
 /// Terminal session type
@@ -25,30 +30,158 @@ pub struct TerminalSessionInfo {
     pub session_type: SessionType,
     pub name: String,
     pub created_at: u64,
+    /// `false` once the session's process is gone (a session restored from
+    /// disk, or one whose process has exited) - it still serves its
+    /// scrollback but can no longer be written to or resized.
+    pub alive: bool,
 }
 
-/// Internal PTY session
-struct PtySession {
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Minimal standard-alphabet base64 encode, so a byte-for-byte scrollback tail
+/// can live in a JSON string field without the 4-9x blowup of serializing it
+/// as an array of integers.
+fn base64_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b0 = chunk[0];
+        let b1 = *chunk.get(1).unwrap_or(&0);
+        let b2 = *chunk.get(2).unwrap_or(&0);
+
+        out.push(BASE64_ALPHABET[(b0 >> 2) as usize] as char);
+        out.push(BASE64_ALPHABET[(((b0 & 0x03) << 4) | (b1 >> 4)) as usize] as char);
+        out.push(if chunk.len() > 1 {
+            BASE64_ALPHABET[(((b1 & 0x0f) << 2) | (b2 >> 6)) as usize] as char
+        } else {
+            '='
+        });
+        out.push(if chunk.len() > 2 {
+            BASE64_ALPHABET[(b2 & 0x3f) as usize] as char
+        } else {
+            '='
+        });
+    }
+    out
+}
+
+/// Inverse of `base64_encode`. Returns `None` on malformed input rather than
+/// panicking, so a corrupt or pre-chunk0-4 persisted file can fall back to
+/// the legacy plain-text representation instead of failing the whole restore.
+fn base64_decode(s: &str) -> Option<Vec<u8>> {
+    fn value(c: u8) -> Option<u8> {
+        BASE64_ALPHABET.iter().position(|&a| a == c).map(|i| i as u8)
+    }
+
+    let s = s.trim_end_matches('=');
+    let mut out = Vec::with_capacity(s.len() * 3 / 4);
+    let chars: Vec<u8> = s.bytes().collect();
+    for chunk in chars.chunks(4) {
+        // A trailing group of exactly 1 character (after stripping '=') can't
+        // encode a full byte - that's corrupt input, not a valid short group.
+        if chunk.len() == 1 {
+            return None;
+        }
+        let vals: Vec<u8> = chunk.iter().map(|&c| value(c)).collect::<Option<_>>()?;
+        out.push((vals[0] << 2) | (vals.get(1).copied().unwrap_or(0) >> 4));
+        if vals.len() > 2 {
+            out.push((vals[1] << 4) | (vals[2] >> 2));
+        }
+        if vals.len() > 3 {
+            out.push((vals[2] << 6) | vals[3]);
+        }
+    }
+    Some(out)
+}
+
+/// On-disk form of a session, written by `serialize_sessions` and read back by
+/// `restore_sessions`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PersistedSession {
     id: String,
     session_type: SessionType,
     name: String,
     created_at: u64,
+    cwd: Option<String>,
+    /// Base64 of the raw scrollback bytes, not a lossily-decoded `String` -
+    /// see `get_scrollback` for why raw bytes matter, and `base64_encode` for
+    /// why this isn't just a `Vec<u8>` (JSON would serialize that as an array
+    /// of integers, multiplying a near-cap 1 MiB scrollback into several MB).
+    scrollback_tail: String,
+}
+
+/// Snapshot of a child process's liveness, returned by `get_session_status`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionStatus {
+    pub running: bool,
+    /// `None` while running, or if the process died without a reportable code.
+    pub exit_code: Option<i32>,
+}
+
+/// The parts of a [`PtySession`] that only exist while its process is running.
+struct LivePty {
     #[allow(dead_code)]
     pty_pair: PtyPair,
     writer: Arc<Mutex<Box<dyn Write + Send>>>,
     output_receiver: mpsc::Receiver<Vec<u8>>,
+    #[allow(dead_code)]
+    app: AppHandle,
+    /// OS process id of the spawned child, used by `send_signal`. `None` on
+    /// platforms/backends where `portable_pty` couldn't report one.
+    pid: Option<u32>,
     _reader_thread: thread::JoinHandle<()>,
+    _watcher_thread: thread::JoinHandle<()>,
+}
+
+/// Internal PTY session
+struct PtySession {
+    id: String,
+    session_type: SessionType,
+    /// Shared with the reader thread so an OSC title sequence can update it
+    /// as soon as it's seen, without going through the manager's mutex.
+    name: Arc<Mutex<String>>,
+    created_at: u64,
+    cwd: Option<String>,
+    scrollback: Arc<Mutex<Scrollback>>,
+    /// Updated by the watcher thread the moment the child exits. Lives here,
+    /// not on `LivePty`, so `get_session_status` can still report the real
+    /// exit code after the watcher nulls out `live`.
+    status: Arc<Mutex<SessionStatus>>,
+    /// `None` for a session restored from disk that couldn't (or, for a local
+    /// shell, never can) be re-attached to a live process, and for a session
+    /// whose process has exited (cleared by the watcher thread).
+    live: Option<LivePty>,
+}
+
+/// A user-defined shell/launcher configuration, so "what process does
+/// `create_local_shell` start" and "what command launches an external
+/// terminal emulator" aren't hardcoded to a fixed list.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TerminalProfile {
+    pub id: String,
+    pub name: String,
+    /// Path to the executable, or a bare name to be resolved against `$PATH`
+    /// (e.g. `fish`, `nu`, or `wsl.exe`).
+    pub exec: String,
+    pub args: Vec<String>,
+    pub env: HashMap<String, String>,
+    pub cwd: Option<String>,
+    /// Written to the PTY once the process has started, e.g. to `cd` into a
+    /// project or run a wrapper script.
+    pub startup_command: Option<String>,
 }
 
 /// Terminal session manager state
 pub struct TerminalManager {
     sessions: HashMap<String, PtySession>,
+    profiles: HashMap<String, TerminalProfile>,
 }
 
 impl TerminalManager {
     pub fn new() -> Self {
         Self {
             sessions: HashMap::new(),
+            profiles: HashMap::new(),
         }
     }
 }
@@ -74,15 +207,198 @@ fn get_default_shell() -> String {
     }
 }
 
+/// Confirm an executable can actually be launched: an absolute/relative path
+/// must exist, and a bare command name must resolve against `$PATH`, the same
+/// way a shell would look it up.
+fn validate_executable(exec: &str) -> Result<(), String> {
+    let path = std::path::Path::new(exec);
+    if path.is_absolute() || exec.contains(std::path::MAIN_SEPARATOR) {
+        return if path.exists() {
+            Ok(())
+        } else {
+            Err(format!("Executable not found: {}", exec))
+        };
+    }
+
+    let found = std::env::var_os("PATH").is_some_and(|paths| {
+        std::env::split_paths(&paths).any(|dir| dir.join(exec).exists())
+    });
+
+    if found {
+        Ok(())
+    } else {
+        Err(format!("Executable not found on PATH: {}", exec))
+    }
+}
+
+/// Spawn the background thread that drains a PTY reader into an output channel
+/// and emits it to the frontend as it arrives.
+///
+/// Shared by every PTY backend (local shell, K8s exec, ...) so they all feed
+/// `read_from_pty`/`get_all_sessions` through the same plumbing. `read_from_pty`
+/// remains as a fallback that drains whatever hasn't been consumed yet, useful
+/// for a UI that reconnects after missing some events.
+fn spawn_output_reader(
+    mut reader: Box<dyn Read + Send>,
+    session_id: String,
+    app: AppHandle,
+    name: Arc<Mutex<String>>,
+    scrollback: Arc<Mutex<Scrollback>>,
+) -> (mpsc::Receiver<Vec<u8>>, thread::JoinHandle<()>) {
+    let (output_sender, output_receiver) = mpsc::channel::<Vec<u8>>();
+
+    let reader_thread = thread::spawn(move || {
+        let mut buffer = vec![0u8; 4096];
+        let mut osc_scanner = OscScanner::new();
+        loop {
+            match reader.read(&mut buffer) {
+                Ok(0) => {
+                    // The child's own exit (and the `terminal://exit` event) is
+                    // reported by the watcher thread spawned alongside this one.
+                    log::debug!("PTY reader EOF for session {}", session_id);
+                    break;
+                }
+                Ok(n) => {
+                    let chunk = &buffer[..n];
+
+                    // Scan for title/bell sequences without consuming the bytes -
+                    // the full chunk below is still forwarded untouched.
+                    for event in osc_scanner.feed(chunk) {
+                        match event {
+                            OscEvent::TitleChanged(title) => {
+                                if let Ok(mut name) = name.lock() {
+                                    *name = title.clone();
+                                }
+                                let _ =
+                                    app.emit(&format!("terminal://title/{}", session_id), title);
+                            }
+                            OscEvent::Bell => {
+                                let _ = app.emit(&format!("terminal://bell/{}", session_id), ());
+                            }
+                        }
+                    }
+
+                    if let Ok(mut scrollback) = scrollback.lock() {
+                        scrollback.push(chunk);
+                    }
+
+                    let chunk = chunk.to_vec();
+                    // Raw bytes, not a lossily-decoded `String`: PTY output is
+                    // arbitrary byte soup (partial UTF-8 sequences split
+                    // across a 4096-byte read boundary, raw binary from a
+                    // `cat`'d file, ...) and lossy conversion would corrupt
+                    // it before the frontend ever sees it.
+                    let _ = app.emit(&format!("terminal://output/{}", session_id), chunk.clone());
+                    if output_sender.send(chunk).is_err() {
+                        log::debug!("PTY receiver dropped for session {}", session_id);
+                        break;
+                    }
+                }
+                Err(e) => {
+                    log::error!("Error reading from PTY: {}", e);
+                    break;
+                }
+            }
+        }
+    });
+
+    (output_receiver, reader_thread)
+}
+
+/// Spawn the background thread that blocks on a child's exit, records its
+/// status into `status` (shared with the owning `PtySession` so it outlives
+/// `live`), emits `terminal://exit/{session_id}` with the real exit code, and
+/// auto-reaps the session's `live` half so it falls back to a dead, replayable
+/// scrollback buffer the same way a restored-from-disk session would.
+fn spawn_child_watcher(
+    mut child: Box<dyn portable_pty::Child + Send + Sync>,
+    session_id: String,
+    app: AppHandle,
+    manager_state: TerminalManagerState,
+    status: Arc<Mutex<SessionStatus>>,
+) -> (Option<u32>, thread::JoinHandle<()>) {
+    let pid = child.process_id();
+    let status_clone = Arc::clone(&status);
+
+    let watcher_thread = thread::spawn(move || {
+        let exit_code = match child.wait() {
+            Ok(exit_status) => Some(exit_status.exit_code() as i32),
+            Err(e) => {
+                log::error!("Failed to wait on child for session {}: {}", session_id, e);
+                None
+            }
+        };
+
+        if let Ok(mut status) = status_clone.lock() {
+            *status = SessionStatus {
+                running: false,
+                exit_code,
+            };
+        }
+
+        let _ = app.emit(&format!("terminal://exit/{}", session_id), exit_code);
+
+        // The caller inserts the session into `manager.sessions` only after
+        // this thread is already spawned, so a process that exits fast enough
+        // (trivially reachable with a profile pointed at something like
+        // `/bin/true`) can have us land here before the entry exists. Retry
+        // briefly instead of silently no-oping, which would otherwise leave
+        // `live` stuck non-null forever on an already-dead session.
+        let mut attempts = 0;
+        loop {
+            {
+                let mut manager = match manager_state.lock() {
+                    Ok(manager) => manager,
+                    Err(_) => break,
+                };
+                if let Some(session) = manager.sessions.get_mut(&session_id) {
+                    session.live = None;
+                    break;
+                }
+            }
+
+            attempts += 1;
+            if attempts >= 50 {
+                log::warn!(
+                    "Session {} never appeared in the manager to reap `live`",
+                    session_id
+                );
+                break;
+            }
+            thread::sleep(std::time::Duration::from_millis(20));
+        }
+    });
+
+    (pid, watcher_thread)
+}
+
 /// Create a new local terminal session
 #[tauri::command]
 pub async fn create_local_shell(
+    app: AppHandle,
     state: State<'_, TerminalManagerState>,
     name: Option<String>,
     cols: Option<u16>,
     rows: Option<u16>,
     initial_command: Option<String>,
+    profile_id: Option<String>,
 ) -> Result<TerminalSessionInfo, String> {
+    let profile = match &profile_id {
+        Some(profile_id) => {
+            let manager = state
+                .lock()
+                .map_err(|e| format!("Failed to lock state: {}", e))?;
+            Some(
+                manager
+                    .profiles
+                    .get(profile_id)
+                    .cloned()
+                    .ok_or_else(|| format!("Terminal profile not found: {}", profile_id))?,
+            )
+        }
+        None => None,
+    };
+
     let pty_system = NativePtySystem::default();
 
     let size = PtySize {
@@ -96,34 +412,50 @@ pub async fn create_local_shell(
         .openpty(size)
         .map_err(|e| format!("Failed to open PTY: {}", e))?;
 
-    let shell = get_default_shell();
+    let shell = profile
+        .as_ref()
+        .map(|p| p.exec.clone())
+        .unwrap_or_else(get_default_shell);
     let mut cmd = CommandBuilder::new(&shell);
 
-    // Set up environment
-    #[cfg(not(target_os = "windows"))]
-    {
+    if let Some(profile) = &profile {
         cmd.env("TERM", "xterm-256color");
-        cmd.env("COLORTERM", "truecolor");
-        // Add shell-specific initialization for interactive mode
-        if shell.contains("zsh") {
-            cmd.args(["-i"]);
-        } else if shell.contains("bash") {
-            cmd.args(["--login", "-i"]);
+        for arg in &profile.args {
+            cmd.arg(arg);
+        }
+        for (key, value) in &profile.env {
+            cmd.env(key, value);
+        }
+        if let Some(cwd) = &profile.cwd {
+            cmd.cwd(cwd);
+        }
+    } else {
+        // Set up environment
+        #[cfg(not(target_os = "windows"))]
+        {
+            cmd.env("TERM", "xterm-256color");
+            cmd.env("COLORTERM", "truecolor");
+            // Add shell-specific initialization for interactive mode
+            if shell.contains("zsh") {
+                cmd.args(["-i"]);
+            } else if shell.contains("bash") {
+                cmd.args(["--login", "-i"]);
+            }
         }
-    }
 
-    #[cfg(target_os = "windows")]
-    {
-        // Windows-specific setup
-        cmd.env("TERM", "xterm-256color");
+        #[cfg(target_os = "windows")]
+        {
+            // Windows-specific setup
+            cmd.env("TERM", "xterm-256color");
+        }
     }
 
-    let _child = pty_pair
+    let child = pty_pair
         .slave
         .spawn_command(cmd)
         .map_err(|e| format!("Failed to spawn shell: {}", e))?;
 
-    let mut reader = pty_pair
+    let reader = pty_pair
         .master
         .try_clone_reader()
         .map_err(|e| format!("Failed to clone PTY reader: {}", e))?;
@@ -143,35 +475,38 @@ pub async fn create_local_shell(
         .as_secs();
 
     let session_name = name.unwrap_or_else(|| format!("Terminal {}", &session_id[..6]));
-
-    // Create a channel for PTY output
-    let (output_sender, output_receiver) = mpsc::channel::<Vec<u8>>();
+    let name_handle = Arc::new(Mutex::new(session_name.clone()));
+    let scrollback = Arc::new(Mutex::new(Scrollback::default()));
+    let cwd = profile.as_ref().and_then(|p| p.cwd.clone()).or_else(|| {
+        std::env::current_dir()
+            .ok()
+            .map(|p| p.to_string_lossy().to_string())
+    });
+    let initial_command =
+        initial_command.or_else(|| profile.as_ref().and_then(|p| p.startup_command.clone()));
 
     // Spawn a background thread to read from PTY
-    let reader_thread = thread::spawn(move || {
-        let mut buffer = vec![0u8; 4096];
-        loop {
-            match reader.read(&mut buffer) {
-                Ok(0) => {
-                    // EOF - PTY closed
-                    log::debug!("PTY reader EOF for session {}", session_id_clone);
-                    break;
-                }
-                Ok(n) => {
-                    // Send the data to the channel
-                    if output_sender.send(buffer[..n].to_vec()).is_err() {
-                        // Receiver dropped, stop reading
-                        log::debug!("PTY receiver dropped for session {}", session_id_clone);
-                        break;
-                    }
-                }
-                Err(e) => {
-                    log::error!("Error reading from PTY: {}", e);
-                    break;
-                }
-            }
-        }
-    });
+    let (output_receiver, reader_thread) = spawn_output_reader(
+        reader,
+        session_id_clone.clone(),
+        app.clone(),
+        Arc::clone(&name_handle),
+        Arc::clone(&scrollback),
+    );
+
+    // Spawn a background thread to wait on the child so the UI learns about
+    // exit/signals without polling.
+    let status = Arc::new(Mutex::new(SessionStatus {
+        running: true,
+        exit_code: None,
+    }));
+    let (pid, watcher_thread) = spawn_child_watcher(
+        child,
+        session_id_clone,
+        app.clone(),
+        state.inner().clone(),
+        Arc::clone(&status),
+    );
 
     // If an initial command was provided, write it to the PTY after a short delay
     if let Some(command) = initial_command {
@@ -194,12 +529,20 @@ pub async fn create_local_shell(
     let session = PtySession {
         id: session_id.clone(),
         session_type: SessionType::Local,
-        name: session_name.clone(),
+        name: name_handle,
         created_at,
-        pty_pair,
-        writer,
-        output_receiver,
-        _reader_thread: reader_thread,
+        cwd,
+        scrollback,
+        status,
+        live: Some(LivePty {
+            pty_pair,
+            writer,
+            output_receiver,
+            app,
+            pid,
+            _reader_thread: reader_thread,
+            _watcher_thread: watcher_thread,
+        }),
     };
 
     let session_info = TerminalSessionInfo {
@@ -207,6 +550,7 @@ pub async fn create_local_shell(
         session_type: SessionType::Local,
         name: session_name,
         created_at,
+        alive: true,
     };
 
     let mut manager = state
@@ -219,6 +563,176 @@ pub async fn create_local_shell(
     Ok(session_info)
 }
 
+/// Open a `kubectl exec` PTY session for a pod/container and wire it into the
+/// same reader/scrollback plumbing as a local shell. Shared by `create_k8s_shell`
+/// and `restore_sessions` (which re-attaches K8s sessions found on disk).
+fn open_k8s_session(
+    app: AppHandle,
+    manager_state: TerminalManagerState,
+    pod: String,
+    container: String,
+    namespace: String,
+    name: Option<String>,
+    shell: Option<String>,
+    cols: Option<u16>,
+    rows: Option<u16>,
+) -> Result<(PtySession, TerminalSessionInfo), String> {
+    if pod.trim().is_empty() || container.trim().is_empty() || namespace.trim().is_empty() {
+        return Err("pod, container, and namespace must all be non-empty".to_string());
+    }
+
+    let pty_system = NativePtySystem::default();
+
+    let size = PtySize {
+        rows: rows.unwrap_or(24),
+        cols: cols.unwrap_or(80),
+        pixel_width: 0,
+        pixel_height: 0,
+    };
+
+    let pty_pair = pty_system
+        .openpty(size)
+        .map_err(|e| format!("Failed to open PTY: {}", e))?;
+
+    // `kubectl exec` is the transport for now; swapping this for a direct
+    // exec-subresource stream (SPDY/WebSocket) is a drop-in replacement as
+    // long as it keeps feeding the same writer/reader pair.
+    let remote_shell = shell.unwrap_or_else(|| "/bin/sh".to_string());
+    let mut cmd = CommandBuilder::new("kubectl");
+    cmd.args([
+        "exec",
+        "-it",
+        &pod,
+        "-c",
+        &container,
+        "-n",
+        &namespace,
+        "--",
+        &remote_shell,
+    ]);
+
+    let child = pty_pair.slave.spawn_command(cmd).map_err(|e| {
+        format!(
+            "Failed to exec into pod {}/{} (container {}): {}",
+            namespace, pod, container, e
+        )
+    })?;
+
+    let reader = pty_pair
+        .master
+        .try_clone_reader()
+        .map_err(|e| format!("Failed to clone PTY reader: {}", e))?;
+
+    let writer = pty_pair
+        .master
+        .take_writer()
+        .map_err(|e| format!("Failed to get PTY writer: {}", e))?;
+
+    let writer = Arc::new(Mutex::new(writer));
+
+    let session_id = Uuid::new_v4().to_string();
+    let session_id_clone = session_id.clone();
+    let created_at = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    let session_name = name.unwrap_or_else(|| format!("{}/{}", pod, container));
+    let name_handle = Arc::new(Mutex::new(session_name.clone()));
+    let scrollback = Arc::new(Mutex::new(Scrollback::default()));
+    let cwd = Some(format!("{}/{}", namespace, pod));
+
+    let (output_receiver, reader_thread) = spawn_output_reader(
+        reader,
+        session_id_clone.clone(),
+        app.clone(),
+        Arc::clone(&name_handle),
+        Arc::clone(&scrollback),
+    );
+
+    let status = Arc::new(Mutex::new(SessionStatus {
+        running: true,
+        exit_code: None,
+    }));
+    let (pid, watcher_thread) = spawn_child_watcher(
+        child,
+        session_id_clone,
+        app.clone(),
+        manager_state,
+        Arc::clone(&status),
+    );
+
+    let session_type = SessionType::K8s {
+        pod,
+        container,
+        namespace,
+    };
+
+    let session = PtySession {
+        id: session_id.clone(),
+        session_type: session_type.clone(),
+        name: name_handle,
+        created_at,
+        cwd,
+        scrollback,
+        status,
+        live: Some(LivePty {
+            pty_pair,
+            writer,
+            output_receiver,
+            app,
+            pid,
+            _reader_thread: reader_thread,
+            _watcher_thread: watcher_thread,
+        }),
+    };
+
+    let session_info = TerminalSessionInfo {
+        id: session_id,
+        session_type,
+        name: session_name,
+        created_at,
+        alive: true,
+    };
+
+    Ok((session, session_info))
+}
+
+/// Create a new terminal session attached to a Kubernetes pod container via `kubectl exec`
+#[tauri::command]
+pub async fn create_k8s_shell(
+    app: AppHandle,
+    state: State<'_, TerminalManagerState>,
+    pod: String,
+    container: String,
+    namespace: String,
+    name: Option<String>,
+    shell: Option<String>,
+    cols: Option<u16>,
+    rows: Option<u16>,
+) -> Result<TerminalSessionInfo, String> {
+    let (session, session_info) = open_k8s_session(
+        app,
+        state.inner().clone(),
+        pod,
+        container,
+        namespace,
+        name,
+        shell,
+        cols,
+        rows,
+    )?;
+
+    let mut manager = state
+        .lock()
+        .map_err(|e| format!("Failed to lock state: {}", e))?;
+    manager.sessions.insert(session_info.id.clone(), session);
+
+    log::info!("Created K8s exec terminal session: {}", session_info.id);
+
+    Ok(session_info)
+}
+
 /// Write data to a terminal session
 #[tauri::command]
 pub async fn write_to_pty(
@@ -235,7 +749,12 @@ pub async fn write_to_pty(
         .get(&session_id)
         .ok_or_else(|| format!("Session not found: {}", session_id))?;
 
-    let mut writer = session
+    let live = session
+        .live
+        .as_ref()
+        .ok_or_else(|| format!("Session {} is not live (process exited or restored from disk)", session_id))?;
+
+    let mut writer = live
         .writer
         .lock()
         .map_err(|e| format!("Failed to lock writer: {}", e))?;
@@ -252,6 +771,10 @@ pub async fn write_to_pty(
 }
 
 /// Read data from a terminal session (non-blocking)
+///
+/// Output is normally delivered via the `terminal://output/{session_id}` event
+/// as it's produced; this command remains as a fallback for a UI that needs to
+/// catch up on whatever has buffered in the channel (e.g. right after reconnecting).
 #[tauri::command]
 pub async fn read_from_pty(
     state: State<'_, TerminalManagerState>,
@@ -266,12 +789,17 @@ pub async fn read_from_pty(
         .get(&session_id)
         .ok_or_else(|| format!("Session not found: {}", session_id))?;
 
+    let live = session
+        .live
+        .as_ref()
+        .ok_or_else(|| format!("Session {} is not live (process exited or restored from disk)", session_id))?;
+
     // Try to receive all available data without blocking
     let mut output = Vec::new();
 
     // Use try_recv to get data without blocking
     loop {
-        match session.output_receiver.try_recv() {
+        match live.output_receiver.try_recv() {
             Ok(data) => {
                 output.extend(data);
             }
@@ -317,8 +845,12 @@ pub async fn resize_pty(
         .get(&session_id)
         .ok_or_else(|| format!("Session not found: {}", session_id))?;
 
-    session
-        .pty_pair
+    let live = session
+        .live
+        .as_ref()
+        .ok_or_else(|| format!("Session {} is not live (process exited or restored from disk)", session_id))?;
+
+    live.pty_pair
         .master
         .resize(PtySize {
             rows,
@@ -338,6 +870,100 @@ pub async fn resize_pty(
     Ok(())
 }
 
+/// Get a session's child process status: whether it's still running and, if
+/// not, its exit code. Backed by `PtySession::status` rather than `live`, so
+/// the real exit code captured by the watcher thread is still reported after
+/// it reaps `live` - a session restored from disk (which never had one)
+/// reports `running: false, exit_code: None` from the same field.
+#[tauri::command]
+pub async fn get_session_status(
+    state: State<'_, TerminalManagerState>,
+    session_id: String,
+) -> Result<SessionStatus, String> {
+    let manager = state
+        .lock()
+        .map_err(|e| format!("Failed to lock state: {}", e))?;
+
+    let session = manager
+        .sessions
+        .get(&session_id)
+        .ok_or_else(|| format!("Session not found: {}", session_id))?;
+
+    session
+        .status
+        .lock()
+        .map(|s| s.clone())
+        .map_err(|e| format!("Failed to lock session status: {}", e))
+}
+
+/// Send a signal to a session's child process. Supports `SIGINT`, `SIGTERM`,
+/// and `SIGKILL` (case-insensitive). Windows has no POSIX signal delivery, so
+/// `SIGKILL` terminates the process outright and the other two are delivered
+/// as a best-effort graceful `taskkill` (without `/F`).
+#[tauri::command]
+pub async fn send_signal(
+    state: State<'_, TerminalManagerState>,
+    session_id: String,
+    signal: String,
+) -> Result<(), String> {
+    let manager = state
+        .lock()
+        .map_err(|e| format!("Failed to lock state: {}", e))?;
+
+    let session = manager
+        .sessions
+        .get(&session_id)
+        .ok_or_else(|| format!("Session not found: {}", session_id))?;
+
+    let live = session
+        .live
+        .as_ref()
+        .ok_or_else(|| format!("Session {} is not live (process exited or restored from disk)", session_id))?;
+
+    let pid = live
+        .pid
+        .ok_or_else(|| format!("Session {} has no known PID", session_id))?;
+
+    #[cfg(unix)]
+    {
+        let sig = match signal.to_uppercase().as_str() {
+            "SIGINT" => libc::SIGINT,
+            "SIGTERM" => libc::SIGTERM,
+            "SIGKILL" => libc::SIGKILL,
+            other => return Err(format!("Unsupported signal: {}", other)),
+        };
+
+        let result = unsafe { libc::kill(pid as libc::pid_t, sig) };
+        if result != 0 {
+            return Err(format!(
+                "Failed to send {} to pid {}: {}",
+                signal,
+                pid,
+                std::io::Error::last_os_error()
+            ));
+        }
+    }
+
+    #[cfg(windows)]
+    {
+        let mut cmd = std::process::Command::new("taskkill");
+        cmd.args(["/PID", &pid.to_string()]);
+        if signal.eq_ignore_ascii_case("SIGKILL") {
+            cmd.arg("/F");
+        } else if !signal.eq_ignore_ascii_case("SIGINT") && !signal.eq_ignore_ascii_case("SIGTERM")
+        {
+            return Err(format!("Unsupported signal: {}", signal));
+        }
+
+        cmd.output()
+            .map_err(|e| format!("Failed to send {} to pid {}: {}", signal, pid, e))?;
+    }
+
+    log::info!("Sent {} to session {} (pid {})", signal, session_id, pid);
+
+    Ok(())
+}
+
 /// Close a terminal session
 #[tauri::command]
 pub async fn close_session(
@@ -371,14 +997,44 @@ pub async fn get_all_sessions(
         .map(|s| TerminalSessionInfo {
             id: s.id.clone(),
             session_type: s.session_type.clone(),
-            name: s.name.clone(),
+            name: s.name.lock().map(|n| n.clone()).unwrap_or_default(),
             created_at: s.created_at,
+            alive: s.live.is_some(),
         })
         .collect();
 
     Ok(sessions)
 }
 
+/// Get the retained scrollback for a session, live or restored from disk.
+///
+/// Raw bytes, not a lossily-decoded `String`: the ring buffer evicts at a byte
+/// boundary that can land in the middle of a multi-byte UTF-8 sequence, and
+/// the session may hold binary output (e.g. a `cat`'d non-text file) to begin
+/// with - same reasoning as the `terminal://output` fix in e2c7947.
+#[tauri::command]
+pub async fn get_scrollback(
+    state: State<'_, TerminalManagerState>,
+    session_id: String,
+) -> Result<Vec<u8>, String> {
+    let manager = state
+        .lock()
+        .map_err(|e| format!("Failed to lock state: {}", e))?;
+
+    let session = manager
+        .sessions
+        .get(&session_id)
+        .ok_or_else(|| format!("Session not found: {}", session_id))?;
+
+    let bytes = session
+        .scrollback
+        .lock()
+        .map_err(|e| format!("Failed to lock scrollback: {}", e))?
+        .snapshot();
+
+    Ok(bytes)
+}
+
 /// Rename a terminal session
 #[tauri::command]
 pub async fn rename_session(
@@ -386,16 +1042,19 @@ pub async fn rename_session(
     session_id: String,
     new_name: String,
 ) -> Result<(), String> {
-    let mut manager = state
+    let manager = state
         .lock()
         .map_err(|e| format!("Failed to lock state: {}", e))?;
 
     let session = manager
         .sessions
-        .get_mut(&session_id)
+        .get(&session_id)
         .ok_or_else(|| format!("Session not found: {}", session_id))?;
 
-    session.name = new_name.clone();
+    *session
+        .name
+        .lock()
+        .map_err(|e| format!("Failed to lock session name: {}", e))? = new_name.clone();
     log::info!("Renamed terminal session {} to {}", session_id, new_name);
 
     Ok(())
@@ -416,9 +1075,233 @@ pub async fn close_all_sessions(state: State<'_, TerminalManagerState>) -> Resul
     Ok(())
 }
 
+fn sessions_file_path(app: &AppHandle) -> Result<std::path::PathBuf, String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data dir: {}", e))?;
+    Ok(dir.join("terminal_sessions.json"))
+}
+
+/// Persist session metadata and scrollback tails to disk so they can be
+/// restored after the app restarts.
+#[tauri::command]
+pub async fn serialize_sessions(
+    app: AppHandle,
+    state: State<'_, TerminalManagerState>,
+) -> Result<(), String> {
+    let persisted: Vec<PersistedSession> = {
+        let manager = state
+            .lock()
+            .map_err(|e| format!("Failed to lock state: {}", e))?;
+
+        manager
+            .sessions
+            .values()
+            .map(|s| PersistedSession {
+                id: s.id.clone(),
+                session_type: s.session_type.clone(),
+                name: s.name.lock().map(|n| n.clone()).unwrap_or_default(),
+                created_at: s.created_at,
+                cwd: s.cwd.clone(),
+                scrollback_tail: s
+                    .scrollback
+                    .lock()
+                    .map(|b| base64_encode(&b.snapshot()))
+                    .unwrap_or_default(),
+            })
+            .collect()
+    };
+
+    let path = sessions_file_path(&app)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create app data dir: {}", e))?;
+    }
+
+    let json = serde_json::to_vec_pretty(&persisted)
+        .map_err(|e| format!("Failed to serialize sessions: {}", e))?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write sessions file: {}", e))?;
+
+    log::info!(
+        "Serialized {} terminal session(s) to {}",
+        persisted.len(),
+        path.display()
+    );
+
+    Ok(())
+}
+
+/// Re-list sessions persisted by `serialize_sessions`. Local sessions come
+/// back as dead, replayable scrollback buffers; K8s sessions attempt a live
+/// `kubectl exec` re-attach under a new session id, and fall back to dead
+/// only if `kubectl` itself fails to start. A pod or container that no
+/// longer exists is not detected here: it comes back as a live session
+/// whose `kubectl` process then exits.
+#[tauri::command]
+pub async fn restore_sessions(
+    app: AppHandle,
+    state: State<'_, TerminalManagerState>,
+) -> Result<Vec<TerminalSessionInfo>, String> {
+    let path = sessions_file_path(&app)?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let json =
+        std::fs::read(&path).map_err(|e| format!("Failed to read sessions file: {}", e))?;
+    let persisted: Vec<PersistedSession> = serde_json::from_slice(&json)
+        .map_err(|e| format!("Failed to parse sessions file: {}", e))?;
+
+    let mut manager = state
+        .lock()
+        .map_err(|e| format!("Failed to lock state: {}", e))?;
+    let mut restored = Vec::with_capacity(persisted.len());
+
+    for entry in persisted {
+        if let SessionType::K8s {
+            pod,
+            container,
+            namespace,
+        } = entry.session_type.clone()
+        {
+            match open_k8s_session(
+                app.clone(),
+                state.inner().clone(),
+                pod,
+                container,
+                namespace,
+                Some(entry.name.clone()),
+                None,
+                None,
+                None,
+            ) {
+                Ok((session, info)) => {
+                    manager.sessions.insert(info.id.clone(), session);
+                    restored.push(info);
+                    continue;
+                }
+                Err(e) => {
+                    log::warn!(
+                        "Could not re-attach K8s session {}, restoring as dead: {}",
+                        entry.id,
+                        e
+                    );
+                }
+            }
+        }
+
+        let scrollback = Arc::new(Mutex::new(Scrollback::default()));
+        if let Ok(mut sb) = scrollback.lock() {
+            // Fall back to treating the field as the raw, pre-chunk0-4 plain
+            // text it would have been in a file written by an older build.
+            let tail = base64_decode(&entry.scrollback_tail)
+                .unwrap_or_else(|| entry.scrollback_tail.as_bytes().to_vec());
+            sb.restore(&tail);
+        }
+
+        let info = TerminalSessionInfo {
+            id: entry.id.clone(),
+            session_type: entry.session_type.clone(),
+            name: entry.name.clone(),
+            created_at: entry.created_at,
+            alive: false,
+        };
+
+        manager.sessions.insert(
+            entry.id.clone(),
+            PtySession {
+                id: entry.id,
+                session_type: entry.session_type,
+                name: Arc::new(Mutex::new(entry.name)),
+                created_at: entry.created_at,
+                cwd: entry.cwd,
+                scrollback,
+                status: Arc::new(Mutex::new(SessionStatus {
+                    running: false,
+                    exit_code: None,
+                })),
+                live: None,
+            },
+        );
+        restored.push(info);
+    }
+
+    log::info!("Restored {} terminal session(s)", restored.len());
+
+    Ok(restored)
+}
+
+/// Create a user-defined terminal profile (a shell/exec path, args, env
+/// overrides, initial cwd, and optional startup command), usable by
+/// `create_local_shell` via `profile_id` and by `launch_external_terminal` via
+/// a `terminal_type` matching its `id` or `name`.
+#[tauri::command]
+pub async fn create_terminal_profile(
+    state: State<'_, TerminalManagerState>,
+    name: String,
+    exec: String,
+    args: Option<Vec<String>>,
+    env: Option<HashMap<String, String>>,
+    cwd: Option<String>,
+    startup_command: Option<String>,
+) -> Result<TerminalProfile, String> {
+    validate_executable(&exec)?;
+
+    let profile = TerminalProfile {
+        id: Uuid::new_v4().to_string(),
+        name,
+        exec,
+        args: args.unwrap_or_default(),
+        env: env.unwrap_or_default(),
+        cwd,
+        startup_command,
+    };
+
+    let mut manager = state
+        .lock()
+        .map_err(|e| format!("Failed to lock state: {}", e))?;
+    manager.profiles.insert(profile.id.clone(), profile.clone());
+
+    log::info!("Created terminal profile {} ({})", profile.id, profile.name);
+
+    Ok(profile)
+}
+
+/// List all registered terminal profiles
+#[tauri::command]
+pub async fn get_terminal_profiles(
+    state: State<'_, TerminalManagerState>,
+) -> Result<Vec<TerminalProfile>, String> {
+    let manager = state
+        .lock()
+        .map_err(|e| format!("Failed to lock state: {}", e))?;
+
+    Ok(manager.profiles.values().cloned().collect())
+}
+
+/// Delete a terminal profile. Sessions already running under it are unaffected.
+#[tauri::command]
+pub async fn delete_terminal_profile(
+    state: State<'_, TerminalManagerState>,
+    profile_id: String,
+) -> Result<(), String> {
+    let mut manager = state
+        .lock()
+        .map_err(|e| format!("Failed to lock state: {}", e))?;
+
+    if manager.profiles.remove(&profile_id).is_some() {
+        log::info!("Deleted terminal profile: {}", profile_id);
+        Ok(())
+    } else {
+        Err(format!("Terminal profile not found: {}", profile_id))
+    }
+}
+
 /// Launch an external terminal application
 #[tauri::command]
 pub async fn launch_external_terminal(
+    state: State<'_, TerminalManagerState>,
     terminal_type: String,
     working_directory: Option<String>,
     command: Option<String>,
@@ -429,6 +1312,36 @@ pub async fn launch_external_terminal(
             .unwrap_or_else(|| ".".to_string())
     });
 
+    let profile = {
+        let manager = state
+            .lock()
+            .map_err(|e| format!("Failed to lock state: {}", e))?;
+        manager
+            .profiles
+            .values()
+            .find(|p| p.id == terminal_type || p.name == terminal_type)
+            .cloned()
+    };
+
+    if let Some(profile) = profile {
+        let mut cmd = std::process::Command::new(&profile.exec);
+        cmd.args(&profile.args);
+        for (key, value) in &profile.env {
+            cmd.env(key, value);
+        }
+        cmd.current_dir(profile.cwd.as_deref().unwrap_or(&cwd));
+
+        if let Some(startup) = command.or_else(|| profile.startup_command.clone()) {
+            cmd.arg(startup);
+        }
+
+        cmd.spawn().map_err(|e| {
+            format!("Failed to launch terminal profile {}: {}", profile.name, e)
+        })?;
+
+        return Ok(());
+    }
+
     #[cfg(target_os = "macos")]
     {
         match terminal_type.as_str() {
@@ -577,3 +1490,41 @@ pub async fn launch_external_terminal(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base64_round_trips_arbitrary_bytes() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        let encoded = base64_encode(&bytes);
+        assert_eq!(base64_decode(&encoded), Some(bytes));
+    }
+
+    #[test]
+    fn base64_round_trips_empty_input() {
+        assert_eq!(base64_encode(&[]), "");
+        assert_eq!(base64_decode(""), Some(Vec::new()));
+    }
+
+    #[test]
+    fn base64_encode_matches_known_vectors() {
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+    }
+
+    #[test]
+    fn base64_decode_rejects_invalid_characters() {
+        assert_eq!(base64_decode("not valid base64!!"), None);
+    }
+
+    #[test]
+    fn base64_decode_rejects_a_truncated_trailing_group() {
+        // "Zg==" (4 chars) decodes to "f" - dropping the "g" leaves a
+        // length-1 trailing group, which can't encode a full byte.
+        assert_eq!(base64_decode("Z=="), None);
+    }
+}
@@ -0,0 +1,161 @@
+//! Stateful scanner for the handful of OSC escape sequences the terminal UI cares
+//! about: OSC 0/1/2 (icon/window/tab title) and the plain BEL alert. It is fed
+//! raw PTY output byte-by-byte and must tolerate a sequence being split across
+//! two reads, since the reader thread only ever sees 4096-byte chunks.
+
+/// An event recognised in the PTY output stream.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OscEvent {
+    /// OSC 0/1/2: the program asked to change the window/tab title.
+    TitleChanged(String),
+    /// A bare BEL (0x07) outside an OSC sequence. A BEL that terminates an
+    /// OSC sequence ends the title and is not reported as a bell.
+    Bell,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+enum ScannerState {
+    #[default]
+    Ground,
+    Escape,
+    OscParam,
+    OscText,
+    OscEscape,
+}
+
+/// Incrementally scans a byte stream for OSC title sequences and BEL bytes.
+///
+/// `feed` never removes or rewrites bytes - it only observes them - so the
+/// caller is free to forward the same chunk to the terminal renderer unchanged.
+#[derive(Default)]
+pub struct OscScanner {
+    state: ScannerState,
+    param: String,
+    text: Vec<u8>,
+}
+
+impl OscScanner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn feed(&mut self, bytes: &[u8]) -> Vec<OscEvent> {
+        let mut events = Vec::new();
+        for &b in bytes {
+            match self.state {
+                ScannerState::Ground => {
+                    if b == 0x07 {
+                        events.push(OscEvent::Bell);
+                    } else if b == 0x1b {
+                        self.state = ScannerState::Escape;
+                    }
+                }
+                ScannerState::Escape => {
+                    if b == b']' {
+                        self.param.clear();
+                        self.text.clear();
+                        self.state = ScannerState::OscParam;
+                    } else {
+                        self.state = ScannerState::Ground;
+                    }
+                }
+                ScannerState::OscParam => {
+                    if b == b';' {
+                        self.state = ScannerState::OscText;
+                    } else if b.is_ascii_digit() {
+                        self.param.push(b as char);
+                    } else {
+                        // Not an OSC sequence we recognise - stop tracking it.
+                        self.state = ScannerState::Ground;
+                    }
+                }
+                ScannerState::OscText => {
+                    if b == 0x07 {
+                        self.finish_title(&mut events);
+                        self.state = ScannerState::Ground;
+                    } else if b == 0x1b {
+                        self.state = ScannerState::OscEscape;
+                    } else {
+                        self.text.push(b);
+                    }
+                }
+                ScannerState::OscEscape => {
+                    // Expecting the ST terminator (ESC \\). Anything else means
+                    // this wasn't a string terminator, so re-evaluate `b` from Ground.
+                    if b == b'\\' {
+                        self.finish_title(&mut events);
+                        self.state = ScannerState::Ground;
+                    } else if b == 0x1b {
+                        self.state = ScannerState::Escape;
+                    } else if b == 0x07 {
+                        events.push(OscEvent::Bell);
+                        self.state = ScannerState::Ground;
+                    } else {
+                        self.state = ScannerState::Ground;
+                    }
+                }
+            }
+        }
+        events
+    }
+
+    fn finish_title(&mut self, events: &mut Vec<OscEvent>) {
+        if matches!(self.param.as_str(), "0" | "1" | "2") {
+            events.push(OscEvent::TitleChanged(
+                String::from_utf8_lossy(&self.text).to_string(),
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn title_terminated_by_bel() {
+        let mut scanner = OscScanner::new();
+        let events = scanner.feed(b"\x1b]0;my title\x07");
+        assert_eq!(events, vec![OscEvent::TitleChanged("my title".to_string())]);
+    }
+
+    #[test]
+    fn title_terminated_by_st() {
+        let mut scanner = OscScanner::new();
+        let events = scanner.feed(b"\x1b]2;other title\x1b\\");
+        assert_eq!(
+            events,
+            vec![OscEvent::TitleChanged("other title".to_string())]
+        );
+    }
+
+    #[test]
+    fn title_sequence_split_across_feed_calls() {
+        let mut scanner = OscScanner::new();
+        assert_eq!(scanner.feed(b"\x1b]0;spl"), vec![]);
+        assert_eq!(scanner.feed(b"it"), vec![]);
+        assert_eq!(
+            scanner.feed(b"\x07"),
+            vec![OscEvent::TitleChanged("split".to_string())]
+        );
+    }
+
+    #[test]
+    fn bare_bell_outside_osc() {
+        let mut scanner = OscScanner::new();
+        assert_eq!(scanner.feed(b"\x07"), vec![OscEvent::Bell]);
+    }
+
+    #[test]
+    fn unrecognized_osc_param_is_ignored() {
+        let mut scanner = OscScanner::new();
+        // OSC 4 (palette change) isn't a title sequence we track.
+        assert_eq!(scanner.feed(b"\x1b]4;ignored\x07"), vec![]);
+    }
+
+    #[test]
+    fn plain_bytes_forward_no_events() {
+        let mut scanner = OscScanner::new();
+        assert_eq!(scanner.feed(b"hello world\n"), vec![]);
+    }
+}
@@ -0,0 +1,93 @@
+//! Bounded ring buffer retaining the tail of a session's PTY output, so a
+//! reconnecting UI (or a session restored from disk) can repaint immediately
+//! instead of starting from a blank screen.
+
+use std::collections::VecDeque;
+
+/// Generous enough for a typical scrollback pane without holding an
+/// unbounded amount of session output in memory.
+pub const DEFAULT_CAP_BYTES: usize = 1024 * 1024;
+
+pub struct Scrollback {
+    cap: usize,
+    buf: VecDeque<u8>,
+}
+
+impl Scrollback {
+    pub fn new(cap: usize) -> Self {
+        Self {
+            cap,
+            buf: VecDeque::with_capacity(cap.min(64 * 1024)),
+        }
+    }
+
+    /// Append new output, dropping the oldest bytes once over the cap.
+    pub fn push(&mut self, bytes: &[u8]) {
+        self.buf.extend(bytes.iter().copied());
+        let overflow = self.buf.len().saturating_sub(self.cap);
+        if overflow > 0 {
+            self.buf.drain(..overflow);
+        }
+    }
+
+    /// Return everything currently retained, oldest first.
+    pub fn snapshot(&self) -> Vec<u8> {
+        self.buf.iter().copied().collect()
+    }
+
+    /// Seed the buffer from a previously persisted tail (e.g. after restoring
+    /// a session from disk), replacing whatever is currently held.
+    pub fn restore(&mut self, bytes: &[u8]) {
+        self.buf.clear();
+        self.push(bytes);
+    }
+}
+
+impl Default for Scrollback {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAP_BYTES)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_then_snapshot_round_trips_under_cap() {
+        let mut sb = Scrollback::new(1024);
+        sb.push(b"hello ");
+        sb.push(b"world");
+        assert_eq!(sb.snapshot(), b"hello world");
+    }
+
+    #[test]
+    fn push_over_cap_evicts_oldest_bytes() {
+        let mut sb = Scrollback::new(5);
+        sb.push(b"abcde");
+        sb.push(b"fg");
+        assert_eq!(sb.snapshot(), b"cdefg");
+    }
+
+    #[test]
+    fn single_push_larger_than_cap_keeps_only_the_tail() {
+        let mut sb = Scrollback::new(3);
+        sb.push(b"abcdefgh");
+        assert_eq!(sb.snapshot(), b"fgh");
+    }
+
+    #[test]
+    fn restore_replaces_existing_contents() {
+        let mut sb = Scrollback::new(1024);
+        sb.push(b"old data");
+        sb.restore(b"restored");
+        assert_eq!(sb.snapshot(), b"restored");
+    }
+
+    #[test]
+    fn restore_respects_cap_like_push() {
+        let mut sb = Scrollback::new(4);
+        sb.restore(b"toolong");
+        assert_eq!(sb.snapshot(), b"long");
+    }
+}